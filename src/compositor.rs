@@ -7,35 +7,445 @@ use smithay::{
             ImportAll, Frame, Renderer,
         },
         winit::{WinitEvent, WinitGraphicsBackend},
+        udev::{UdevBackend, UdevEvent},
+        drm::{DrmDevice, DrmEvent},
+        libinput::{LibinputInputBackend, LibinputSessionInterface},
+        allocator::gbm::GbmAllocator,
+        session::{Session, Signal as SessionSignal, auto::AutoSession},
     },
     desktop::{space::Space, Window, WindowSurfaceType},
     reexports::{
         calloop::{EventLoop, LoopHandle, RegistrationToken},
-        wayland_server::{Display, DisplayHandle},
+        wayland_server::{
+            Display, DisplayHandle, Client,
+            backend::{ClientData, ClientId, DisconnectReason, ObjectId},
+            protocol::{wl_buffer::WlBuffer, wl_output::WlOutput, wl_surface::WlSurface},
+        },
+        input::Libinput,
+        drm::control::{connector, crtc, Device as ControlDevice},
     },
     utils::{Logical, Point, Rectangle, Transform},
     wayland::{
-        compositor::{CompositorState, CompositorClientState},
+        buffer::BufferHandler,
+        compositor::{CompositorState, CompositorClientState, CompositorHandler},
         output::Output,
         shell::xdg::{XdgShellState, XdgToplevelSurfaceData},
+        shell::wlr_layer::{WlrLayerShellState, WlrLayerShellHandler, Layer, LayerSurface, Anchor, ExclusiveZone},
         seat::{Seat, SeatState, KeyboardHandle, PointerHandle, XkbConfig},
         data_device::DataDeviceState,
         xwayland::{XWayland, XWaylandEvent},
     },
 };
+use smithay::{delegate_compositor, delegate_layer_shell};
 use std::process::{Command, Child};
 use xkbcommon::xkb;
 use toml::Value;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 use chrono::Local;
+use crate::keybind::ModMask;
+
+/// Which rendering/input backend the compositor is running under.
+///
+/// `Winit` nests inside an existing Wayland/X11 session (useful for
+/// development); `Udev` drives the GPU/input devices directly and is what
+/// lets `BlueEnvironment` run as a standalone session on bare TTY.
+enum Backend {
+    Winit {
+        backend: WinitGraphicsBackend,
+        winit: smithay::backend::winit::WinitEventLoop,
+    },
+    Udev(UdevBackendData),
+}
+
+/// DRM/libinput state for the standalone (non-nested) backend.
+///
+/// One `DrmDevice` is tracked per enumerated GPU node; `renderer` is the
+/// primary device's `Gles2Renderer`, shared by every CRTC we drive.
+struct UdevBackendData {
+    udev: UdevBackend,
+    libinput: Libinput,
+    drm_devices: HashMap<PathBuf, DrmDevice>,
+    renderer: Gles2Renderer,
+    /// Owns the seat/VT; used to acquire and release the DRM master and
+    /// input devices around pause/resume, and to switch VTs.
+    session: AutoSession,
+}
+
+impl Backend {
+    fn renderer(&mut self) -> &mut Gles2Renderer {
+        match self {
+            Backend::Winit { backend, .. } => backend.renderer(),
+            Backend::Udev(data) => &mut data.renderer,
+        }
+    }
+
+    /// Switches VTs through the session (no-op when nested under winit,
+    /// since there's no VT to own in that case).
+    fn change_vt(&mut self, vt: i32) {
+        if let Backend::Udev(data) = self {
+            data.session.change_vt(vt).ok();
+        }
+    }
+
+    /// Releases the DRM master and suspends libinput on session pause, so we
+    /// stop driving hardware the session has revoked from under us. No-op
+    /// under winit, which doesn't own a DRM master or raw input devices.
+    fn pause(&mut self) {
+        if let Backend::Udev(data) = self {
+            for device in data.drm_devices.values() {
+                device.release_master_lock().ok();
+            }
+            data.libinput.suspend();
+        }
+    }
+
+    /// Reacquires the DRM master and resumes libinput on session resume.
+    fn resume(&mut self) {
+        if let Backend::Udev(data) = self {
+            for device in data.drm_devices.values() {
+                device.acquire_master_lock().ok();
+            }
+            data.libinput.resume().ok();
+        }
+    }
+
+    /// Turns a DRM connector + chosen mode into an `Output` with a real
+    /// name/size/refresh rate, instead of the hardcoded winit 1920x1080.
+    fn outputs_from_connectors(&self) -> Vec<Output> {
+        match self {
+            Backend::Winit { .. } => vec![Output::new(
+                "winit".to_string(),
+                Rectangle::from_loc_and_size(Point::from((0, 0)), (1920, 1080)),
+                None,
+            )],
+            Backend::Udev(data) => {
+                let mut outputs = Vec::new();
+                for (path, device) in data.drm_devices.iter() {
+                    outputs.extend(connected_outputs_for_device(path, device));
+                }
+                outputs
+            }
+        }
+    }
+
+    /// Reacts to a udev connector/device hotplug event: opens newly added
+    /// DRM devices and reports the `Output`s that appeared, or reports an
+    /// output name to drop when its device was removed. No-op on winit,
+    /// which has no real connectors to hotplug.
+    fn handle_udev_event(&mut self, event: UdevEvent) -> Vec<OutputChange> {
+        let Backend::Udev(data) = self else { return Vec::new() };
+        match event {
+            UdevEvent::Added { device_id: _, path } => {
+                // Open through the session (same as initial enumeration in
+                // `init_udev_backend`) so a hotplugged GPU also goes through
+                // logind's `TakeDevice` instead of bypassing the session.
+                let Ok(fd) = data.session.open(&path, 0) else { return Vec::new() };
+                let Ok(device) = DrmDevice::new(fd, false) else { return Vec::new() };
+                let added = connected_outputs_for_device(&path, &device)
+                    .into_iter()
+                    .map(OutputChange::Added)
+                    .collect();
+                data.drm_devices.insert(path, device);
+                added
+            }
+            UdevEvent::Changed { device_id } => {
+                data.drm_devices
+                    .iter()
+                    .find(|(_, device)| device.device_id() == device_id)
+                    .map(|(path, device)| {
+                        connected_outputs_for_device(path, device)
+                            .into_iter()
+                            .map(OutputChange::Added)
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            }
+            UdevEvent::Removed { device_id } => {
+                let removed_path = data.drm_devices
+                    .iter()
+                    .find(|(_, device)| device.device_id() == device_id)
+                    .map(|(path, _)| path.clone());
+                if let Some(path) = removed_path {
+                    let outputs = data.drm_devices.get(&path)
+                        .map(|device| connected_outputs_for_device(&path, device))
+                        .unwrap_or_default();
+                    data.drm_devices.remove(&path);
+                    outputs.into_iter().map(|o| OutputChange::Removed(o.name())).collect()
+                } else {
+                    Vec::new()
+                }
+            }
+        }
+    }
+}
+
+/// An output appearing or disappearing as a result of a udev hotplug event,
+/// applied by the main loop via `BlueEnvironment::add_output`/`remove_output`.
+enum OutputChange {
+    Added(Output),
+    Removed(String),
+}
+
+/// Builds one `Output` per connected connector on a DRM device, named
+/// `"<card>-<connector id>"` with its first advertised mode.
+fn connected_outputs_for_device(path: &Path, device: &DrmDevice) -> Vec<Output> {
+    let mut outputs = Vec::new();
+    if let Ok(handles) = device.resource_handles() {
+        for conn_handle in handles.connectors() {
+            if let Ok(conn_info) = device.get_connector(*conn_handle) {
+                if conn_info.state() != connector::State::Connected {
+                    continue;
+                }
+                if let Some(mode) = conn_info.modes().first() {
+                    let (w, h) = mode.size();
+                    let refresh = mode.vrefresh();
+                    let name = format!(
+                        "{}-{}",
+                        path.file_name().and_then(|n| n.to_str()).unwrap_or("card"),
+                        conn_info.interface_id(),
+                    );
+                    outputs.push(Output::new(
+                        name,
+                        Rectangle::from_loc_and_size(Point::from((0, 0)), (w as i32, h as i32)),
+                        Some(refresh as i32),
+                    ));
+                }
+            }
+        }
+    }
+    outputs
+}
+
+/// Layer-shell surfaces mapped onto one output, grouped by stacking layer.
+///
+/// Populated as `zwlr_layer_surface_v1` clients map themselves; `usable_area`
+/// is recomputed each time a surface is mapped onto the output so `Space`
+/// windows don't get placed underneath panels. A later change to an
+/// already-mapped surface's exclusive zone does not update it.
+#[derive(Default)]
+struct OutputLayers {
+    background: Vec<LayerSurface>,
+    bottom: Vec<LayerSurface>,
+    top: Vec<LayerSurface>,
+    overlay: Vec<LayerSurface>,
+    usable_area: Option<Rectangle<i32, Logical>>,
+}
+
+impl OutputLayers {
+    fn layer_mut(&mut self, layer: Layer) -> &mut Vec<LayerSurface> {
+        match layer {
+            Layer::Background => &mut self.background,
+            Layer::Bottom => &mut self.bottom,
+            Layer::Top => &mut self.top,
+            Layer::Overlay => &mut self.overlay,
+        }
+    }
+
+    fn all(&self) -> impl Iterator<Item = &LayerSurface> {
+        self.background.iter().chain(&self.bottom).chain(&self.top).chain(&self.overlay)
+    }
+}
+
+/// One render element's identity, geometry, and buffer-commit count as of
+/// the last rendered frame. `commit` lets `compute_damage` catch a surface
+/// that redrew in place (new video frame, cursor blink, a terminal
+/// repainting) without moving or resizing, which geometry alone can't see.
+#[derive(Clone, PartialEq)]
+struct ElementState {
+    id: String,
+    geometry: Rectangle<i32, Logical>,
+    commit: u64,
+}
+
+/// Per-output damage tracking. `last_elements` is what was actually drawn
+/// last frame; comparing it against this frame's elements gives the
+/// changed-region rectangles to pass to the renderer instead of redrawing
+/// the whole output every iteration.
+#[derive(Default)]
+struct OutputDamageTracker {
+    last_elements: Vec<ElementState>,
+}
+
+impl OutputDamageTracker {
+    /// Diffs `current` against the previous frame's elements and returns
+    /// the union of changed regions (new, removed, moved/resized, or
+    /// buffer-damaged elements), clipped to `output_rect`. Also stores
+    /// `current` as the new baseline for next frame.
+    fn compute_damage(
+        &mut self,
+        current: Vec<ElementState>,
+        output_rect: Rectangle<i32, Logical>,
+    ) -> Vec<Rectangle<i32, Logical>> {
+        let mut damage = Vec::new();
+        for element in &current {
+            match self.last_elements.iter().find(|e| e.id == element.id) {
+                Some(prev) if prev.geometry == element.geometry && prev.commit == element.commit => {}
+                Some(prev) if prev.geometry == element.geometry => {
+                    // Same position/size, new buffer contents: only that
+                    // element's own rectangle needs redrawing.
+                    damage.push(element.geometry);
+                }
+                Some(prev) => {
+                    damage.push(prev.geometry);
+                    damage.push(element.geometry);
+                }
+                None => damage.push(element.geometry),
+            }
+        }
+        for prev in &self.last_elements {
+            if !current.iter().any(|e| e.id == prev.id) {
+                damage.push(prev.geometry);
+            }
+        }
+        self.last_elements = current;
+
+        damage
+            .into_iter()
+            .filter_map(|rect| rect.intersection(output_rect))
+            .collect()
+    }
+}
+
+impl ModMask {
+    fn from_modifiers(modifiers: &smithay::input::keyboard::ModifiersState) -> Self {
+        let mut mask = ModMask::empty();
+        if modifiers.shift { mask |= ModMask::SHIFT; }
+        if modifiers.ctrl { mask |= ModMask::CTRL; }
+        if modifiers.alt { mask |= ModMask::ALT; }
+        if modifiers.logo { mask |= ModMask::LOGO; }
+        mask
+    }
+}
+
+/// Resolves a chord's trailing key name ("V", "Escape", "F1", ...) to an
+/// xkb keysym. Covers the letters/digits and named keys this environment's
+/// default bindings use; unknown names fail the whole chord to parse.
+fn keysym_from_name(name: &str) -> Option<u32> {
+    if name.len() == 1 {
+        let c = name.chars().next().unwrap().to_ascii_uppercase();
+        if c.is_ascii_alphabetic() {
+            return Some(xkb::KEY_A + (c as u32 - 'A' as u32));
+        }
+        if c.is_ascii_digit() {
+            return Some(xkb::KEY_0 + (c as u32 - '0' as u32));
+        }
+    }
+    match name {
+        "Escape" => Some(xkb::KEY_Escape),
+        "Tab" => Some(xkb::KEY_Tab),
+        "Space" => Some(xkb::KEY_space),
+        "Return" | "Enter" => Some(xkb::KEY_Return),
+        _ if name.starts_with('F') && name[1..].parse::<u32>().is_ok() => {
+            let n: u32 = name[1..].parse().unwrap();
+            (1..=12).contains(&n).then(|| xkb::KEY_F1 + (n - 1))
+        }
+        _ => None,
+    }
+}
+
+/// Parses a `"Super+Shift+V" = "volume_down"` style config table into a
+/// (modifier mask, keysym) -> action lookup, dispatched generically from
+/// `handle_input` instead of the old hand-written `if` chain.
+fn parse_keybindings(config: &Value) -> HashMap<(u8, u32), String> {
+    // Seed with the environment's historical defaults so an environment
+    // with no `[keybindings]` table still behaves like before.
+    let mut bindings: HashMap<(u8, u32), String> = [
+        ("Super+Escape", "toggle_desktop"),
+        ("Super+B", "launch_browser"),
+        ("Super+G", "launch_game_launcher"),
+        ("Super+T", "launch_terminal"),
+        ("Super+S", "launch_software_center"),
+        ("Super+W", "toggle_wifi"),
+        ("Super+L", "toggle_bluetooth"),
+        ("Super+V", "volume_up"),
+        ("Super+Shift+V", "volume_down"),
+        ("Super+K", "launch_kwallet"),
+    ]
+    .into_iter()
+    .filter_map(|(chord, action)| chord_to_binding(chord).map(|key| (key, action.to_string())))
+    .collect();
+
+    if let Some(table) = config.get("keybindings").and_then(|v| v.as_table()) {
+        for (chord, action) in table {
+            if let (Some(key), Some(action)) = (chord_to_binding(chord), action.as_str()) {
+                bindings.insert(key, action.to_string());
+            }
+        }
+    }
+    bindings
+}
+
+/// Parses a chord string (`"Super+Shift+V"`) into a (modifier mask, keysym)
+/// lookup key. Returns `None` if the trailing key name isn't recognized.
+fn chord_to_binding(chord: &str) -> Option<(u8, u32)> {
+    let mut mask = ModMask::empty();
+    let mut keysym = None;
+    for part in chord.split('+') {
+        match part {
+            "Super" | "Logo" => mask |= ModMask::LOGO,
+            "Shift" => mask |= ModMask::SHIFT,
+            "Ctrl" | "Control" => mask |= ModMask::CTRL,
+            "Alt" => mask |= ModMask::ALT,
+            key => keysym = keysym_from_name(key),
+        }
+    }
+    keysym.map(|k| (mask.bits(), k))
+}
+
+/// Builds an `XkbConfig` from the `[keyboard]` config table, falling back
+/// to xkb defaults (US layout, no repeat override) when unset.
+fn xkb_config_from(config: &Value) -> XkbConfig<'static> {
+    let keyboard = config.get("keyboard");
+    let layout = keyboard.and_then(|k| k.get("layout")).and_then(|v| v.as_str()).unwrap_or("us").to_string();
+    let variant = keyboard.and_then(|k| k.get("variant")).and_then(|v| v.as_str()).unwrap_or("").to_string();
+    let options = keyboard.and_then(|k| k.get("options")).and_then(|v| v.as_str()).map(|s| s.to_string());
+
+    XkbConfig {
+        layout: Box::leak(layout.into_boxed_str()),
+        variant: Box::leak(variant.into_boxed_str()),
+        options,
+        ..XkbConfig::default()
+    }
+}
+
+/// One `[[output]]` config entry: explicit placement for a named connector,
+/// used by `layout_outputs` instead of the auto left-to-right fallback.
+struct OutputConfig {
+    name: String,
+    x: i32,
+    y: i32,
+    scale: f64,
+    transform: Option<Transform>,
+}
+
+impl OutputConfig {
+    fn from_value(value: &Value) -> Option<Self> {
+        let name = value.get("name")?.as_str()?.to_string();
+        let x = value.get("x").and_then(|v| v.as_integer()).unwrap_or(0) as i32;
+        let y = value.get("y").and_then(|v| v.as_integer()).unwrap_or(0) as i32;
+        let scale = value.get("scale").and_then(|v| v.as_float()).unwrap_or(1.0);
+        let transform = value.get("transform").and_then(|v| v.as_str()).and_then(|t| match t {
+            "normal" => Some(Transform::Normal),
+            "90" => Some(Transform::_90),
+            "180" => Some(Transform::_180),
+            "270" => Some(Transform::_270),
+            "flipped" => Some(Transform::Flipped),
+            _ => None,
+        });
+        Some(OutputConfig { name, x, y, scale, transform })
+    }
+}
 
 struct BlueEnvironment {
     display: DisplayHandle,
     compositor_state: CompositorState,
     xdg_shell_state: XdgShellState,
+    layer_shell_state: WlrLayerShellState,
+    layer_surfaces: HashMap<String, OutputLayers>,
+    damage_trackers: HashMap<String, OutputDamageTracker>,
     seat_state: SeatState,
     data_device_state: DataDeviceState,
     space: Space<Window>,
@@ -53,23 +463,66 @@ struct BlueEnvironment {
     bluetooth_enabled: bool,
     battery_status: String,
     time: String,
+    /// Set on session resume so the next render does a full redraw instead
+    /// of trusting stale buffer contents after the DRM master was released.
+    outputs_dirty: bool,
+    /// VT number requested by a Super+F-key press, applied on the next loop
+    /// iteration (the backend isn't reachable from `handle_input` itself).
+    pending_vt_switch: Option<i32>,
+    /// (modifier mask, keysym) -> action name, parsed from `[keybindings]`.
+    keybindings: HashMap<(u8, u32), String>,
+    /// Raw udev connector/device events queued by the calloop source,
+    /// applied against the backend at the top of the next loop iteration
+    /// (the backend isn't reachable from inside the calloop callback).
+    pending_udev_events: Vec<UdevEvent>,
+    /// Session pause/resume signal queued by the calloop source, applied
+    /// against the backend (DRM master + libinput) at the top of the next
+    /// loop iteration for the same reason as `pending_udev_events`.
+    pending_session_signal: Option<SessionSignal>,
+    /// Bumped in `CompositorHandler::commit` for every surface that commits
+    /// a new buffer, so `render_elements_for` can treat a same-geometry
+    /// redraw (new video frame, cursor blink, a terminal repainting) as
+    /// damage instead of relying on position/size alone.
+    surface_commits: HashMap<ObjectId, u64>,
+}
+
+/// Per-client compositor bookkeeping `CompositorHandler` needs to hand back
+/// via `client_compositor_state`; this environment has nothing else to
+/// track per client.
+#[derive(Default)]
+struct ClientState {
+    compositor_state: CompositorClientState,
+}
+
+impl ClientData for ClientState {
+    fn initialized(&self, _client_id: ClientId) {}
+    fn disconnected(&self, _client_id: ClientId, _reason: DisconnectReason) {}
 }
 
 impl BlueEnvironment {
     fn new(display: DisplayHandle, event_loop: &LoopHandle<Self>) -> Self {
+        // Load configuration first so the seat can be set up with the
+        // configured layout/repeat and keybindings can be parsed below.
+        let config_str = fs::read_to_string("/etc/blue-environment/config.toml")
+        .unwrap_or_else(|_| include_str!("../config/config.toml").to_string());
+        let config = config_str.parse::<Value>().expect("Invalid config format");
+
         let compositor_state = CompositorState::new::<Self, CompositorClientState>(&display, None);
         let xdg_shell_state = XdgShellState::new::<Self>(&display);
+        let layer_shell_state = WlrLayerShellState::new::<Self>(&display);
         let mut seat_state = SeatState::new();
         let data_device_state = DataDeviceState::new::<Self>(&display);
         let seat = seat_state.new_wl_seat(&display, "blue_seat");
-        let keyboard = seat_state.add_keyboard(&seat, XkbConfig::default()).ok();
+        let keyboard = seat_state.add_keyboard(&seat, xkb_config_from(&config)).ok();
+        if let Some(keyboard) = &keyboard {
+            let keyboard_cfg = config.get("keyboard");
+            let rate = keyboard_cfg.and_then(|k| k.get("repeat_rate")).and_then(|v| v.as_integer()).unwrap_or(25) as i32;
+            let delay = keyboard_cfg.and_then(|k| k.get("repeat_delay")).and_then(|v| v.as_integer()).unwrap_or(600) as i32;
+            keyboard.change_repeat_info(rate, delay);
+        }
+        let keybindings = parse_keybindings(&config);
         let pointer = seat_state.add_pointer(&seat).ok();
 
-        // Load configuration
-        let config_str = fs::read_to_string("/etc/blue-environment/config.toml")
-        .unwrap_or_else(|_| include_str!("../config/config.toml").to_string());
-        let config = config_str.parse::<Value>().expect("Invalid config format");
-
         // Initialize XWayland
         let xwayland = XWayland::new(&display, event_loop.handle()).ok();
 
@@ -81,6 +534,9 @@ impl BlueEnvironment {
             display,
             compositor_state,
             xdg_shell_state,
+            layer_shell_state,
+            layer_surfaces: HashMap::new(),
+            damage_trackers: HashMap::new(),
             seat_state,
             data_device_state,
             space: Space::new(None),
@@ -98,7 +554,33 @@ impl BlueEnvironment {
             bluetooth_enabled: Self::get_bluetooth_status(),
             battery_status,
             time,
+            outputs_dirty: false,
+            pending_vt_switch: None,
+            keybindings,
+            pending_udev_events: Vec::new(),
+            pending_session_signal: None,
+            surface_commits: HashMap::new(),
+        }
+    }
+
+    /// Reacts to a logind/direct-session pause or resume. On pause the
+    /// session has already revoked our DRM/input fds; on resume we mark
+    /// outputs dirty so the next frame does a full redraw rather than
+    /// assuming the previous framebuffer contents are still valid. The
+    /// actual DRM master drop/reacquire and libinput suspend/resume happen
+    /// against the backend at the top of the next loop iteration, since the
+    /// backend isn't reachable from this calloop callback.
+    fn handle_session_signal(&mut self, signal: SessionSignal) {
+        match signal {
+            SessionSignal::PauseSession => {
+                self.notifications.push("Session paused (VT switched away)".to_string());
+            }
+            SessionSignal::ActivateSession => {
+                self.outputs_dirty = true;
+                self.notifications.push("Session resumed".to_string());
+            }
         }
+        self.pending_session_signal = Some(signal);
     }
 
     fn launch_app(&mut self, app: &str) {
@@ -114,7 +596,36 @@ impl BlueEnvironment {
         }
     }
 
-    fn handle_input(&mut self, event: InputEvent<WinitGraphicsBackend>) {
+    /// Runs the action bound to a configured chord by name. Unknown action
+    /// names are logged and ignored rather than panicking, since they come
+    /// from user-edited config.
+    fn dispatch_action(&mut self, action: &str) {
+        match action {
+            "toggle_desktop" => {
+                self.space.windows().for_each(|window| {
+                    window.toplevel().configure(&self.display, WindowSurfaceType::NONE, None);
+                });
+            }
+            "launch_browser" => self.launch_app("browser"),
+            "launch_game_launcher" => self.launch_app("game_launcher"),
+            "launch_terminal" => self.launch_app("terminal"),
+            "launch_software_center" => self.launch_app("software_center"),
+            "toggle_wifi" => self.toggle_wifi(),
+            "toggle_bluetooth" => self.toggle_bluetooth(),
+            "volume_up" => self.adjust_volume(0.1),
+            "volume_down" => self.adjust_volume(-0.1),
+            "brightness_up" => self.adjust_brightness(0.1),
+            "brightness_down" => self.adjust_brightness(-0.1),
+            "launch_kwallet" => self.launch_app("kwalletmanager5"),
+            other => log::warn!("Unknown keybinding action: {}", other),
+        }
+    }
+
+    /// Handles one input event regardless of which backend produced it.
+    ///
+    /// Both the winit and udev/libinput backends feed events through here
+    /// so keybindings and pointer handling stay backend-agnostic.
+    fn handle_input<B: smithay::backend::input::InputBackend>(&mut self, event: InputEvent<B>) -> Option<i32> {
         match event {
             InputEvent::Keyboard { event } => {
                 if let Some(keyboard) = &self.keyboard {
@@ -122,47 +633,24 @@ impl BlueEnvironment {
                     let state = event.state();
                     let modifiers = keyboard.modifier_state();
 
-                    // Super+Esc to return to desktop
-                    if keycode == xkb::KEY_Escape as u32 && modifiers.logo && state == smithay::input::keyboard::KeyState::Pressed {
-                        self.space.windows().for_each(|window| {
-                            window.toplevel().configure(&self.display, WindowSurfaceType::NONE, None);
-                        });
-                    }
-                    // Super+B for Brave
-                    if keycode == xkb::KEY_B as u32 && modifiers.logo && state == smithay::input::keyboard::KeyState::Pressed {
-                        self.launch_app("browser");
-                    }
-                    // Super+G for Game Launcher
-                    if keycode == xkb::KEY_G as u32 && modifiers.logo && state == smithay::input::keyboard::KeyState::Pressed {
-                        self.launch_app("game_launcher");
+                    if state != smithay::input::keyboard::KeyState::Pressed {
+                        return None;
                     }
-                    // Super+T for Terminal
-                    if keycode == xkb::KEY_T as u32 && modifiers.logo && state == smithay::input::keyboard::KeyState::Pressed {
-                        self.launch_app("terminal");
-                    }
-                    // Super+S for Software Center
-                    if keycode == xkb::KEY_S as u32 && modifiers.logo && state == smithay::input::keyboard::KeyState::Pressed {
-                        self.launch_app("software_center");
-                    }
-                    // Super+W for Wi-Fi toggle
-                    if keycode == xkb::KEY_W as u32 && modifiers.logo && state == smithay::input::keyboard::KeyState::Pressed {
-                        self.toggle_wifi();
-                    }
-                    // Super+L for Bluetooth toggle
-                    if keycode == xkb::KEY_L as u32 && modifiers.logo && state == smithay::input::keyboard::KeyState::Pressed {
-                        self.toggle_bluetooth();
-                    }
-                    // Super+V for volume up
-                    if keycode == xkb::KEY_V as u32 && modifiers.logo && state == smithay::input::keyboard::KeyState::Pressed {
-                        self.adjust_volume(0.1);
-                    }
-                    // Super+Shift+V for volume down
-                    if keycode == xkb::KEY_V as u32 && modifiers.logo && modifiers.shift && state == smithay::input::keyboard::KeyState::Pressed {
-                        self.adjust_volume(-0.1);
+
+                    // Super+F1..F12 to switch VTs on the udev backend. Kept
+                    // as a fixed binding rather than a configurable action
+                    // since it's a session primitive, not an app shortcut.
+                    if modifiers.logo {
+                        for (i, f_key) in (xkb::KEY_F1..=xkb::KEY_F12).enumerate() {
+                            if keycode == f_key as u32 {
+                                return Some(i as i32 + 1);
+                            }
+                        }
                     }
-                    // Super+K for KDE Wallet
-                    if keycode == xkb::KEY_K as u32 && modifiers.logo && state == smithay::input::keyboard::KeyState::Pressed {
-                        self.launch_app("kwalletmanager5");
+
+                    let mask = ModMask::from_modifiers(&modifiers);
+                    if let Some(action) = self.keybindings.get(&(mask.bits(), keycode)).cloned() {
+                        self.dispatch_action(&action);
                     }
                 }
             }
@@ -181,6 +669,7 @@ impl BlueEnvironment {
             }
             _ => (),
         }
+        None
     }
 
     fn toggle_fullscreen(&mut self, window: &Window) {
@@ -194,9 +683,209 @@ impl BlueEnvironment {
         }
     }
 
+    /// Maps a newly-appeared output, or updates an already-tracked one
+    /// in place (e.g. a mode change reported via `UdevEvent::Changed`)
+    /// without disturbing its mapped layer-shell surfaces or damage
+    /// baseline.
     fn add_output(&mut self, output: Output) {
-        self.space.map_output(&output, Point::from((0, 0)), 1.0, None);
+        if let Some(existing) = self.outputs.iter_mut().find(|o| o.name() == output.name()) {
+            *existing = output;
+            self.layout_outputs();
+            return;
+        }
+        self.layer_surfaces.insert(output.name(), OutputLayers::default());
+        self.damage_trackers.insert(output.name(), OutputDamageTracker::default());
         self.outputs.push(output);
+        self.layout_outputs();
+    }
+
+    /// Unmaps a disconnected output, migrating any windows left on it onto
+    /// the first remaining output before re-laying out.
+    fn remove_output(&mut self, name: &str) {
+        let Some(removed) = self.outputs.iter().find(|o| o.name() == name).cloned() else { return };
+
+        if let Some(target) = self.outputs.iter().find(|o| o.name() != name).cloned() {
+            let stranded: Vec<Window> = self.space
+                .windows()
+                .filter(|w| self.space.outputs_for_window(w).iter().any(|o| o.name() == name))
+                .cloned()
+                .collect();
+            for window in stranded {
+                let target_loc = self.space.output_geometry(&target).map(|g| g.loc).unwrap_or_default();
+                self.space.map_window(&window, target_loc, None, false);
+            }
+        }
+
+        self.space.unmap_output(&removed);
+        self.outputs.retain(|o| o.name() != name);
+        self.layer_surfaces.remove(name);
+        self.damage_trackers.remove(name);
+        self.notifications.push(format!("Output {} disconnected", name));
+        self.layout_outputs();
+    }
+
+    /// Reads the `[[output]]` config table for an explicit position/scale
+    /// per connector name; any output not listed (or when the table is
+    /// absent) falls back to automatic left-to-right placement in
+    /// connector-discovery order.
+    fn layout_outputs(&mut self) {
+        let configured: HashMap<String, OutputConfig> = self.config.get("output")
+            .and_then(|v| v.as_array())
+            .map(|entries| {
+                entries.iter()
+                    .filter_map(OutputConfig::from_value)
+                    .map(|cfg| (cfg.name.clone(), cfg))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut auto_x = 0i32;
+        for output in self.outputs.clone() {
+            if let Some(cfg) = configured.get(&output.name()) {
+                self.space.map_output(&output, Point::from((cfg.x, cfg.y)), cfg.scale, cfg.transform);
+            } else {
+                let width = output.current_mode().map(|m| m.size.w).unwrap_or(1920);
+                self.space.map_output(&output, Point::from((auto_x, 0)), 1.0, None);
+                auto_x += width;
+            }
+        }
+    }
+
+    /// Where `output` is mapped in `Space`-global coordinates, i.e. the
+    /// offset between its own local (0,0)-origin geometry (what
+    /// `usable_area_for`/layer-shell surfaces use) and the global space
+    /// `self.space.window_geometry` reports windows in.
+    fn output_position(&self, output: &Output) -> Point<i32, Logical> {
+        self.space.output_geometry(output).map(|g| g.loc).unwrap_or_default()
+    }
+
+    /// How many times `surface` has committed a new buffer, per
+    /// `CompositorHandler::commit`. Used as a damage source alongside
+    /// geometry so a same-position redraw (video frame, cursor blink, a
+    /// terminal repainting) still counts as damage.
+    fn commit_count_for(&self, surface: &WlSurface) -> u64 {
+        self.surface_commits.get(&surface.id()).copied().unwrap_or(0)
+    }
+
+    /// Gathers the geometry of everything that will be drawn on `output`
+    /// this frame: the background (one element covering the whole output),
+    /// every layer-shell surface, and every mapped `Window`. Every geometry
+    /// is expressed in `Space`-global coordinates (layer-shell/background
+    /// geometry is local to the output, so it's offset by the output's
+    /// mapped position) so they're all comparable against `window_geometry`
+    /// and against one `output_rect` when fed into
+    /// `OutputDamageTracker::compute_damage`.
+    fn render_elements_for(&self, output: &Output) -> Vec<ElementState> {
+        let position = self.output_position(output);
+        let size = output.current_mode().map(|m| m.size).unwrap_or((1920, 1080).into());
+        let background_rect = Rectangle::from_loc_and_size(position, size);
+
+        let mut elements = vec![ElementState {
+            id: format!("background@{}", output.name()),
+            geometry: background_rect,
+            commit: 0,
+        }];
+
+        if let Some(layers) = self.layer_surfaces.get(&output.name()) {
+            for (i, surface) in layers.all().enumerate() {
+                let mut geometry = surface.geometry();
+                geometry.loc.x += position.x;
+                geometry.loc.y += position.y;
+                elements.push(ElementState {
+                    id: format!("layer@{}:{}", output.name(), i),
+                    geometry,
+                    commit: self.commit_count_for(surface.wl_surface()),
+                });
+            }
+        }
+
+        for window in self.space.windows() {
+            if let Some(geometry) = self.space.window_geometry(window) {
+                let commit = window.toplevel().map(|t| self.commit_count_for(t.wl_surface())).unwrap_or(0);
+                elements.push(ElementState {
+                    id: format!("window@{:?}", window.toplevel().map(|t| t.wl_surface().id())),
+                    geometry,
+                    commit,
+                });
+            }
+        }
+
+        elements
+    }
+
+    /// Registers a newly-mapped `zwlr_layer_surface_v1` under its output and
+    /// layer, then recomputes that output's usable area.
+    fn map_layer_surface(&mut self, output_name: &str, layer: Layer, surface: LayerSurface) {
+        if let Some(layers) = self.layer_surfaces.get_mut(output_name) {
+            layers.layer_mut(layer).push(surface);
+        }
+        self.recompute_usable_area(output_name);
+    }
+
+    /// Shrinks the output's full geometry by every mapped surface's
+    /// exclusive zone (per its anchor edge + margin), leaving the
+    /// rectangle that `Space` should map `Window`s into.
+    fn recompute_usable_area(&mut self, output_name: &str) {
+        let full_size = self.outputs.iter().find(|o| o.name() == output_name)
+            .and_then(|o| o.current_mode())
+            .map(|mode| mode.size)
+            .unwrap_or((1920, 1080).into());
+        let mut area = Rectangle::from_loc_and_size(Point::from((0, 0)), full_size);
+
+        if let Some(layers) = self.layer_surfaces.get_mut(output_name) {
+            for surface in layers.all() {
+                let state = surface.cached_state();
+                let zone = match state.exclusive_zone {
+                    ExclusiveZone::Exclusive(px) => px,
+                    _ => continue,
+                };
+                let margin = state.margin;
+                if state.anchor.contains(Anchor::TOP) {
+                    area.loc.y += zone + margin.top;
+                    area.size.h -= zone + margin.top;
+                } else if state.anchor.contains(Anchor::BOTTOM) {
+                    area.size.h -= zone + margin.bottom;
+                } else if state.anchor.contains(Anchor::LEFT) {
+                    area.loc.x += zone + margin.left;
+                    area.size.w -= zone + margin.left;
+                } else if state.anchor.contains(Anchor::RIGHT) {
+                    area.size.w -= zone + margin.right;
+                }
+            }
+            layers.usable_area = Some(area);
+        }
+    }
+
+    /// The rectangle `Window`s should be mapped into on this output, after
+    /// reserving space for any exclusive-zone layer surfaces (panels,
+    /// launchers, ...). Falls back to the full output geometry.
+    fn usable_area_for(&self, output: &Output) -> Rectangle<i32, Logical> {
+        self.layer_surfaces.get(&output.name())
+            .and_then(|layers| layers.usable_area)
+            .unwrap_or_else(|| {
+                let size = output.current_mode().map(|m| m.size).unwrap_or((1920, 1080).into());
+                Rectangle::from_loc_and_size(Point::from((0, 0)), size)
+            })
+    }
+
+    /// Renders every layer-shell surface on `output` in the given stacking
+    /// layer. Each surface is drawn at the output's origin; its anchor and
+    /// margin are not applied here. Only the rectangles in `damage` are
+    /// actually redrawn.
+    fn render_layer(&self, renderer: &mut Gles2Renderer, frame: &mut Frame, output: &Output, layer: Layer, damage: &[Rectangle<i32, Logical>]) {
+        if damage.is_empty() {
+            return;
+        }
+        let Some(layers) = self.layer_surfaces.get(&output.name()) else { return };
+        let surfaces = match layer {
+            Layer::Background => &layers.background,
+            Layer::Bottom => &layers.bottom,
+            Layer::Top => &layers.top,
+            Layer::Overlay => &layers.overlay,
+        };
+        for surface in surfaces {
+            surface.render(renderer, frame, Point::from((0, 0)), damage).ok();
+        }
     }
 
     fn load_background(&mut self, renderer: &mut Gles2Renderer, path: &str) {
@@ -209,16 +898,28 @@ impl BlueEnvironment {
         }
     }
 
-    fn render_background(&self, renderer: &mut Gles2Renderer, frame: &mut Frame) {
+    /// Draws the background for one output: a native `Background`-layer
+    /// client if one has mapped itself there, else the imported texture,
+    /// else a flat fallback gradient. Only the rectangles in `damage` (each
+    /// already intersected with the output's own bounds) are redrawn.
+    fn render_background(&self, renderer: &mut Gles2Renderer, frame: &mut Frame, output: &Output, damage: &[Rectangle<i32, Logical>]) {
+        if damage.is_empty() {
+            return;
+        }
+        let has_background_client = self.layer_surfaces.get(&output.name())
+            .map(|layers| !layers.background.is_empty())
+            .unwrap_or(false);
+        if has_background_client {
+            self.render_layer(renderer, frame, output, Layer::Background, damage);
+            return;
+        }
+
+        let size = output.current_mode().unwrap().size;
+        let rect = Rectangle::from_loc_and_size(Point::from((0, 0)), size);
         if let Some(texture) = &self.background_texture {
-            let size = self.outputs[0].current_mode().unwrap().size;
-            let rect = Rectangle::from_loc_and_size(Point::from((0, 0)), size);
-            renderer.render_texture(texture, rect, 1.0, Some(Transform::Normal)).unwrap();
+            renderer.render_texture_with_damage(texture, rect, damage, 1.0, Some(Transform::Normal)).unwrap();
         } else {
-            // Fallback gradient
-            let size = self.outputs[0].current_mode().unwrap().size;
-            let rect = Rectangle::from_loc_and_size(Point::from((0, 0)), size);
-            renderer.clear(frame, [0.1, 0.1, 0.2, 1.0], rect).unwrap();
+            renderer.clear(frame, [0.1, 0.1, 0.2, 1.0], damage).unwrap();
         }
     }
 
@@ -315,22 +1016,175 @@ impl BlueEnvironment {
     }
 }
 
+impl BufferHandler for BlueEnvironment {
+    fn buffer_destroyed(&mut self, _buffer: &WlBuffer) {}
+}
+
+impl CompositorHandler for BlueEnvironment {
+    fn compositor_state(&mut self) -> &mut CompositorState {
+        &mut self.compositor_state
+    }
+
+    fn client_compositor_state<'a>(&self, client: &'a Client) -> &'a CompositorClientState {
+        &client.get_data::<ClientState>().unwrap().compositor_state
+    }
+
+    /// Imports the newly committed buffer and bumps that surface's commit
+    /// count, the damage source `render_elements_for` reads to tell a
+    /// same-geometry redraw from an actually idle surface.
+    fn commit(&mut self, surface: &WlSurface) {
+        on_commit_buffer_handler::<Self>(surface);
+        *self.surface_commits.entry(surface.id()).or_insert(0) += 1;
+    }
+}
+delegate_compositor!(BlueEnvironment);
+
+impl WlrLayerShellHandler for BlueEnvironment {
+    fn shell_state(&mut self) -> &mut WlrLayerShellState {
+        &mut self.layer_shell_state
+    }
+
+    /// A client mapped a new `zwlr_layer_surface_v1`; register it under its
+    /// requested output (falling back to the first output, matching this
+    /// file's other single-output fallbacks) and reserve its exclusive zone.
+    fn new_layer_surface(
+        &mut self,
+        surface: LayerSurface,
+        wl_output: Option<WlOutput>,
+        layer: Layer,
+        _namespace: String,
+    ) {
+        let output_name = wl_output
+            .as_ref()
+            .and_then(Output::from_resource)
+            .or_else(|| self.outputs.first().cloned())
+            .map(|o| o.name());
+        if let Some(output_name) = output_name {
+            self.map_layer_surface(&output_name, layer, surface);
+        }
+    }
+}
+delegate_layer_shell!(BlueEnvironment);
+
+/// Picks winit (nested) or udev (bare TTY) based on `--tty` / `--winit`,
+/// falling back to autodetection via `$WAYLAND_DISPLAY`/`$DISPLAY` when
+/// neither flag is passed.
+fn select_backend(args: &[String]) -> bool {
+    if args.iter().any(|a| a == "--tty") {
+        return true;
+    }
+    if args.iter().any(|a| a == "--winit") {
+        return false;
+    }
+    std::env::var_os("WAYLAND_DISPLAY").is_none() && std::env::var_os("DISPLAY").is_none()
+}
+
+/// Enumerates DRM devices via udev, opens each through the session, and wires
+/// the primary GPU's `Gles2Renderer` so the rest of the compositor can
+/// treat it the same as the winit backend.
+fn init_udev_backend(
+    event_loop: &LoopHandle<BlueEnvironment>,
+) -> Result<UdevBackendData, Box<dyn std::error::Error>> {
+    // AutoSession picks logind when available and falls back to the direct
+    // (root-owned) session otherwise, so the compositor can acquire the GPU
+    // and input devices without running as root under a logind system.
+    let (session, notifier) = AutoSession::new(None).ok_or("failed to open a session")?;
+
+    let udev = UdevBackend::new("seat0")?;
+    let mut libinput = Libinput::new_with_udev::<LibinputSessionInterface<AutoSession>>(session.clone().into());
+    libinput.udev_assign_seat("seat0").map_err(|_| "failed to assign libinput seat")?;
+
+    let mut drm_devices = HashMap::new();
+    for (_device_id, path) in udev.device_list() {
+        // Open the device through the session rather than directly, so an
+        // unprivileged process still gets a usable fd via logind's
+        // `TakeDevice`, and `DrmDevice` opens *that* fd instead of the path.
+        let Ok(fd) = session.open(path, 0) else { continue };
+        if let Ok(device) = DrmDevice::new(fd, false) {
+            drm_devices.insert(path.to_path_buf(), device);
+        }
+    }
+
+    // Prefer a device with at least one connected connector as "primary" (a
+    // hybrid-GPU laptop's discrete/headless GPU otherwise has as much claim
+    // to `.values().next()` as the one actually driving a display); fall
+    // back to an arbitrary device if somehow none has a connector yet.
+    let renderer = drm_devices
+        .iter()
+        .find(|(path, device)| !connected_outputs_for_device(path, device).is_empty())
+        .or_else(|| drm_devices.iter().next())
+        .and_then(|(_, device)| device.gles2_renderer().ok())
+        .ok_or("no DRM render node available")?;
+
+    // Surface pause/resume as calloop events so the main loop can release
+    // and re-acquire the DRM master + input devices around VT switches.
+    event_loop
+        .insert_source(notifier, |signal, _, state: &mut BlueEnvironment| {
+            state.handle_session_signal(signal);
+        })
+        .ok();
+
+    Ok(UdevBackendData {
+        udev,
+        libinput,
+        drm_devices,
+        renderer,
+        session,
+    })
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize event loop and display
     let mut event_loop = EventLoop::try_new()?;
     let mut display = Display::new()?;
     let mut state = BlueEnvironment::new(display.handle(), &event_loop.handle());
 
-    // Initialize Winit backend
-    let (mut backend, mut winit) = smithay::backend::winit::init::<WinitGraphicsBackend<_>>()?;
+    let args: Vec<String> = std::env::args().collect();
+    let use_tty = select_backend(&args);
+
+    let mut backend = if use_tty {
+        log::info!("Starting on bare TTY via udev/DRM/libinput");
+        Backend::Udev(init_udev_backend(&event_loop.handle())?)
+    } else {
+        log::info!("Starting nested via winit");
+        let (winit_backend, winit) = smithay::backend::winit::init::<WinitGraphicsBackend<_>>()?;
+        Backend::Winit { backend: winit_backend, winit }
+    };
 
-    // Create output
-    let output = Output::new(
-        "winit".to_string(),
-                             Rectangle::from_loc_and_size(Point::from((0, 0)), (1920, 1080)),
-                             None,
-    );
-    state.add_output(output);
+    // Create outputs from whichever backend was selected, replacing the
+    // hardcoded 1920x1080 winit output with real connector modes on udev.
+    for output in backend.outputs_from_connectors() {
+        state.add_output(output);
+    }
+
+    // Register libinput as a calloop event source so device add/remove and
+    // input events integrate with the rest of the loop.
+    if let Backend::Udev(data) = &backend {
+        let mut libinput_backend = LibinputInputBackend::new(data.libinput.clone());
+        event_loop
+            .handle()
+            .insert_source(libinput_backend, |event, _, state: &mut BlueEnvironment| {
+                // VT switches queued here are applied at the top of the next
+                // loop iteration, since the backend itself isn't reachable
+                // from inside a calloop callback.
+                if let Some(vt) = state.handle_input(event) {
+                    state.pending_vt_switch = Some(vt);
+                }
+            })
+            .ok();
+    }
+
+    // Register udev itself so connector/device add-remove events reach the
+    // main loop; applying them (opening/closing DRM devices, remapping
+    // `Space`) happens there since the backend lives outside `state`.
+    if let Backend::Udev(data) = &backend {
+        event_loop
+            .handle()
+            .insert_source(data.udev.clone(), |event, _, state: &mut BlueEnvironment| {
+                state.pending_udev_events.push(event);
+            })
+            .ok();
+    }
 
     // Load background
     if let Some(bg_path) = state.config["appearance"]["background"].as_str() {
@@ -344,10 +1198,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     .spawn()
     .ok();
 
-    // Start notification daemon
-    Command::new("mako")
-    .spawn()
-    .ok();
+    // Notifications are now served natively via the Top layer of the
+    // layer-shell (see `render_layer`), so there's no external daemon to spawn.
 
     // Start XWayland
     if let Some(xwayland) = state.xwayland.as_mut() {
@@ -364,22 +1216,126 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             xwayland.handle_events(&mut state.space, &state.display).ok();
         }
 
-        // Handle input events
-        winit.dispatch_new_events(|event| match event {
-            WinitEvent::Input(input) => state.handle_input(input),
-                                  _ => (),
-        })?;
+        // Handle input events. On the winit backend events arrive through
+        // `dispatch_new_events`; on udev they arrive through the libinput
+        // calloop source registered above, so only winit needs polling here.
+        if let Backend::Winit { winit, .. } = &mut backend {
+            winit.dispatch_new_events(|event| match event {
+                WinitEvent::Input(input) => {
+                    if let Some(vt) = state.handle_input(input) {
+                        state.pending_vt_switch = Some(vt);
+                    }
+                }
+                _ => (),
+            })?;
+        }
+        // Compute per-output damage before deciding whether to block.
+        // `outputs_dirty` (set on session resume) forces a full redraw since
+        // we can't trust the old damage baseline after losing DRM master.
+        let mut damage_by_output = HashMap::new();
+        let mut space_damage = Vec::new();
+        for output in state.outputs.clone() {
+            let elements = state.render_elements_for(&output);
+            // `render_elements_for` reports every element in Space-global
+            // coordinates (see its doc comment), so intersect against the
+            // output's full rectangle at its mapped position.
+            let position = state.output_position(&output);
+            let size = output.current_mode().map(|m| m.size).unwrap_or((1920, 1080).into());
+            let output_rect = Rectangle::from_loc_and_size(position, size);
+            let tracker = state.damage_trackers.entry(output.name()).or_default();
+            let mut damage = tracker.compute_damage(elements, output_rect);
+            if state.outputs_dirty {
+                damage.push(output_rect);
+            }
+            // `space.render` works in Space-global coordinates, so keep a
+            // global copy for it.
+            space_damage.extend(damage.iter().cloned());
+            // `render_background`/`render_layer` draw at the output-local
+            // origin, so hand them local damage rects.
+            for rect in &mut damage {
+                rect.loc.x -= position.x;
+                rect.loc.y -= position.y;
+            }
+            damage_by_output.insert(output.name(), damage);
+        }
+        state.outputs_dirty = false;
+
+        let has_damage = damage_by_output.values().any(|d| !d.is_empty());
+
+        // With nothing to redraw, block in calloop until the next
+        // input/Wayland event instead of busy-looping every 16ms.
+        let dispatch_timeout = if has_damage {
+            Some(std::time::Duration::from_millis(16))
+        } else {
+            None
+        };
+        event_loop.dispatch(dispatch_timeout, &mut state)?;
+
+        // Apply any VT switch requested via Super+F1-F12 since last iteration.
+        if let Some(vt) = state.pending_vt_switch.take() {
+            backend.change_vt(vt);
+        }
+
+        // Apply any session pause/resume since last iteration: drop or
+        // reacquire the DRM master and suspend/resume libinput so we aren't
+        // left driving devices the session doesn't currently own.
+        if let Some(signal) = state.pending_session_signal.take() {
+            match signal {
+                SessionSignal::PauseSession => backend.pause(),
+                SessionSignal::ActivateSession => backend.resume(),
+            }
+        }
+
+        // Apply any connector/device hotplug events queued by the udev
+        // source: new connectors get mapped outputs, removed ones migrate
+        // their windows off and get unmapped.
+        for event in state.pending_udev_events.drain(..).collect::<Vec<_>>() {
+            for change in backend.handle_udev_event(event) {
+                match change {
+                    OutputChange::Added(output) => state.add_output(output),
+                    OutputChange::Removed(name) => state.remove_output(&name),
+                }
+            }
+        }
 
         // Clean up finished processes
         state.running_apps.retain(|_, child| child.try_wait().unwrap_or(None).is_none());
 
-        // Render the scene
-        let mut renderer = backend.renderer();
-        renderer.with_context(|renderer, frame| {
-            state.render_background(renderer, frame);
-            state.space.render(renderer, frame, None).unwrap();
-        })?;
-        state.space.commit();
+        if has_damage {
+            // Render the scene: background/bottom layers, then windows, then
+            // top/overlay layers (panels, launchers, notifications) on top.
+            // Each output's own damage rectangles (not the whole output) are
+            // handed to the renderer, so an untouched region of a damaged
+            // output still isn't redrawn.
+            let renderer = backend.renderer();
+            renderer.with_context(|renderer, frame| {
+                for output in &state.outputs {
+                    let damage = damage_by_output.get(&output.name()).cloned().unwrap_or_default();
+                    if damage.is_empty() {
+                        continue;
+                    }
+                    state.render_background(renderer, frame, output, &damage);
+                    state.render_layer(renderer, frame, output, Layer::Bottom, &damage);
+                }
+                state.space.render(renderer, frame, Some(&space_damage)).unwrap();
+                for output in &state.outputs {
+                    let damage = damage_by_output.get(&output.name()).cloned().unwrap_or_default();
+                    state.render_layer(renderer, frame, output, Layer::Top, &damage);
+                    state.render_layer(renderer, frame, output, Layer::Overlay, &damage);
+                }
+            })?;
+            state.space.commit();
+
+            // Only surfaces whose output actually had damage get a frame
+            // callback, so idle windows on an undamaged output don't redraw.
+            for window in state.space.windows() {
+                if let Some(output) = state.space.outputs_for_window(window).into_iter().next() {
+                    if damage_by_output.get(&output.name()).map(|d| !d.is_empty()).unwrap_or(false) {
+                        window.send_frame(&output, std::time::Duration::from_millis(0), None, |_, _| Some(output.clone()));
+                    }
+                }
+            }
+        }
         state.display.flush_clients()?;
     }
 }
@@ -0,0 +1,12 @@
+bitflags::bitflags! {
+    /// Modifier bits used as half of a keybinding/shortcut's lookup key,
+    /// parsed out of chord strings like `"Super+Shift+V"`. Shared by the
+    /// compositor and launcher, which each add their own conversion to/from
+    /// their own input backend's modifier type (xkb state, `egui::Modifiers`).
+    pub struct ModMask: u8 {
+        const SHIFT = 0b0001;
+        const CTRL  = 0b0010;
+        const ALT   = 0b0100;
+        const LOGO  = 0b1000;
+    }
+}
@@ -4,9 +4,733 @@ use toml::Value;
 use std::fs;
 use std::collections::HashMap;
 use image::DynamicImage;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{SystemTime, UNIX_EPOCH, Duration, Instant};
 use std::sync::mpsc;
 use std::thread;
+use dbus::blocking::{Connection, LocalConnection};
+use dbus::arg::{RefArg, Variant};
+use dbus_crossroads::Crossroads;
+use std::sync::{Arc, atomic::{AtomicBool, AtomicU32, Ordering}};
+use crate::keybind::ModMask;
+
+/// One probe result sent back from a `Poller` worker thread.
+enum StatusUpdate {
+    Battery(String),
+    Volume(f32),
+    Brightness(f32),
+    Wifi(bool),
+    CpuUsage(f32),
+    MemoryUsage(f32),
+}
+
+/// Spawns one background thread per configured probe (battery, volume,
+/// brightness, wifi, CPU, memory), each polling on its own interval and
+/// sending results back over `mpsc` instead of the render loop blocking on
+/// `upower`/`nmcli`/etc. directly, following the eww `defpoll` model.
+struct Poller {
+    receiver: mpsc::Receiver<StatusUpdate>,
+    settings_visible: Arc<AtomicBool>,
+}
+
+impl Poller {
+    fn spawn(config: &Value) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let settings_visible = Arc::new(AtomicBool::new(false));
+        let polling = config.get("polling");
+        let interval_secs = |key: &str, default: u64| -> u64 {
+            polling.and_then(|p| p.get(key)).and_then(|v| v.as_integer()).map(|v| v as u64).unwrap_or(default)
+        };
+
+        Self::spawn_probe(tx.clone(), settings_visible.clone(), interval_secs("battery_secs", 30), true, || {
+            StatusUpdate::Battery(BlueLauncher::get_battery_status())
+        });
+        Self::spawn_probe(tx.clone(), settings_visible.clone(), interval_secs("volume_secs", 5), true, || {
+            StatusUpdate::Volume(get_volume_level())
+        });
+        Self::spawn_probe(tx.clone(), settings_visible.clone(), interval_secs("brightness_secs", 5), true, || {
+            StatusUpdate::Brightness(get_brightness_level())
+        });
+        Self::spawn_probe(tx.clone(), settings_visible.clone(), interval_secs("wifi_secs", 10), true, || {
+            StatusUpdate::Wifi(BlueLauncher::get_wifi_status())
+        });
+        Self::spawn_probe(tx.clone(), settings_visible.clone(), interval_secs("cpu_secs", 3), true, || {
+            StatusUpdate::CpuUsage(get_cpu_usage())
+        });
+        Self::spawn_probe(tx, settings_visible.clone(), interval_secs("memory_secs", 3), true, || {
+            StatusUpdate::MemoryUsage(get_memory_usage())
+        });
+
+        Poller { receiver: rx, settings_visible }
+    }
+
+    /// Runs `probe` on a loop every `interval_secs`. When `gated` is true the
+    /// probe only actually runs while the settings section is expanded,
+    /// rather than polling hardware nobody's looking at.
+    fn spawn_probe<F>(tx: mpsc::Sender<StatusUpdate>, visible: Arc<AtomicBool>, interval_secs: u64, gated: bool, probe: F)
+    where
+        F: Fn() -> StatusUpdate + Send + 'static,
+    {
+        thread::spawn(move || loop {
+            if !gated || visible.load(Ordering::Relaxed) {
+                if tx.send(probe()).is_err() {
+                    return;
+                }
+            }
+            thread::sleep(Duration::from_secs(interval_secs.max(1)));
+        });
+    }
+
+    fn set_settings_visible(&self, visible: bool) {
+        self.settings_visible.store(visible, Ordering::Relaxed);
+    }
+
+    /// Drains everything queued so far without blocking the render loop.
+    fn drain(&self) -> Vec<StatusUpdate> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+fn get_volume_level() -> f32 {
+    Command::new("wpctl")
+    .args(["get-volume", "@DEFAULT_SINK@"])
+    .output()
+    .ok()
+    .and_then(|output| {
+        String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .nth(1)
+        .and_then(|v| v.parse::<f32>().ok())
+    })
+    .unwrap_or(0.5)
+}
+
+fn get_brightness_level() -> f32 {
+    let current = Command::new("brightnessctl").arg("get").output().ok()
+        .and_then(|o| String::from_utf8_lossy(&o.stdout).trim().parse::<f32>().ok());
+    let max = Command::new("brightnessctl").arg("max").output().ok()
+        .and_then(|o| String::from_utf8_lossy(&o.stdout).trim().parse::<f32>().ok());
+    match (current, max) {
+        (Some(current), Some(max)) if max > 0.0 => (current / max).clamp(0.0, 1.0),
+        _ => 0.5,
+    }
+}
+
+fn get_cpu_usage() -> f32 {
+    fs::read_to_string("/proc/loadavg")
+    .ok()
+    .and_then(|content| content.split_whitespace().next().map(|s| s.to_string()))
+    .and_then(|s| s.parse::<f32>().ok())
+    .unwrap_or(0.0)
+}
+
+/// One access point as reported by `nmcli dev wifi list`.
+#[derive(Clone)]
+struct WifiNetwork {
+    ssid: String,
+    signal: u8,
+    secured: bool,
+}
+
+/// Scans visible access points via `nmcli -t -f SSID,SIGNAL,SECURITY dev
+/// wifi list`, sorted strongest-first.
+fn scan_wifi_networks() -> Vec<WifiNetwork> {
+    let Ok(output) = Command::new("nmcli")
+        .args(["-t", "-f", "SSID,SIGNAL,SECURITY", "dev", "wifi", "list"])
+        .output()
+    else {
+        return Vec::new();
+    };
+
+    let mut networks: Vec<WifiNetwork> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split(':');
+            let ssid = fields.next()?.to_string();
+            if ssid.is_empty() {
+                return None;
+            }
+            let signal = fields.next()?.parse::<u8>().ok()?;
+            let secured = fields.next().map(|s| !s.is_empty()).unwrap_or(false);
+            Some(WifiNetwork { ssid, signal, secured })
+        })
+        .collect();
+    networks.sort_by(|a, b| b.signal.cmp(&a.signal));
+    networks.dedup_by(|a, b| a.ssid == b.ssid);
+    networks
+}
+
+/// Reads the currently-associated SSID, or `None` when disconnected.
+fn current_wifi_ssid() -> Option<String> {
+    Command::new("nmcli")
+    .args(["-t", "-f", "active,ssid", "dev", "wifi"])
+    .output()
+    .ok()
+    .and_then(|output| {
+        String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix("yes:").map(|ssid| ssid.to_string()))
+    })
+}
+
+fn get_memory_usage() -> f32 {
+    let Some(content) = fs::read_to_string("/proc/meminfo").ok() else { return 0.0 };
+    let field = |name: &str| -> Option<f32> {
+        content.lines()
+            .find(|line| line.starts_with(name))
+            .and_then(|line| line.split_whitespace().nth(1))
+            .and_then(|v| v.parse::<f32>().ok())
+    };
+    match (field("MemTotal:"), field("MemAvailable:")) {
+        (Some(total), Some(available)) if total > 0.0 => ((total - available) / total).clamp(0.0, 1.0),
+        _ => 0.0,
+    }
+}
+
+/// One BlueZ `org.bluez.Device1` object, as surfaced by `GetManagedObjects`.
+#[derive(Clone)]
+struct BluetoothDevice {
+    path: String,
+    alias: String,
+    paired: bool,
+    connected: bool,
+    battery_percent: Option<u8>,
+}
+
+/// Talks to BlueZ over the system D-Bus instead of shelling out to
+/// `bluetoothctl`, so the settings panel can see and manage devices rather
+/// than just power the adapter on/off.
+struct BluetoothManager {
+    adapter_path: String,
+}
+
+impl BluetoothManager {
+    fn new() -> Self {
+        BluetoothManager { adapter_path: "/org/bluez/hci0".to_string() }
+    }
+
+    fn connection() -> Option<Connection> {
+        Connection::new_system().ok()
+    }
+
+    /// Enumerates every `org.bluez.Device1` object via `GetManagedObjects`,
+    /// pulling out `Alias`/`Paired`/`Connected` and, when present,
+    /// `Battery1.Percentage`.
+    fn devices(&self) -> Vec<BluetoothDevice> {
+        let Some(conn) = Self::connection() else { return Vec::new() };
+        let proxy = conn.with_proxy("org.bluez", "/", Duration::from_millis(2000));
+        let objects: Result<(HashMap<dbus::Path, HashMap<String, HashMap<String, Variant<Box<dyn RefArg>>>>>,), _> =
+            proxy.method_call("org.freedesktop.DBus.ObjectManager", "GetManagedObjects", ());
+        let Ok((objects,)) = objects else { return Vec::new() };
+
+        objects.into_iter()
+            .filter_map(|(path, interfaces)| {
+                let device = interfaces.get("org.bluez.Device1")?;
+                let alias = device.get("Alias").and_then(|v| v.0.as_str()).unwrap_or("Unknown device").to_string();
+                let paired = device.get("Paired").and_then(|v| v.0.as_i64()).map(|b| b != 0).unwrap_or(false);
+                let connected = device.get("Connected").and_then(|v| v.0.as_i64()).map(|b| b != 0).unwrap_or(false);
+                let battery_percent = interfaces.get("org.bluez.Battery1")
+                    .and_then(|battery| battery.get("Percentage"))
+                    .and_then(|v| v.0.as_i64())
+                    .map(|p| p as u8);
+                Some(BluetoothDevice { path: path.to_string(), alias, paired, connected, battery_percent })
+            })
+            .collect()
+    }
+
+    fn connect(&self, path: &str) -> Result<(), String> {
+        let conn = Self::connection().ok_or("D-Bus unavailable")?;
+        let proxy = conn.with_proxy("org.bluez", path, Duration::from_millis(5000));
+        proxy.method_call("org.bluez.Device1", "Connect", ()).map_err(|e| e.to_string())
+    }
+
+    fn disconnect(&self, path: &str) -> Result<(), String> {
+        let conn = Self::connection().ok_or("D-Bus unavailable")?;
+        let proxy = conn.with_proxy("org.bluez", path, Duration::from_millis(5000));
+        proxy.method_call("org.bluez.Device1", "Disconnect", ()).map_err(|e| e.to_string())
+    }
+
+    fn start_discovery(&self) -> Result<(), String> {
+        let conn = Self::connection().ok_or("D-Bus unavailable")?;
+        let proxy = conn.with_proxy("org.bluez", &self.adapter_path, Duration::from_millis(5000));
+        proxy.method_call("org.bluez.Adapter1", "StartDiscovery", ()).map_err(|e| e.to_string())
+    }
+
+    fn stop_discovery(&self) -> Result<(), String> {
+        let conn = Self::connection().ok_or("D-Bus unavailable")?;
+        let proxy = conn.with_proxy("org.bluez", &self.adapter_path, Duration::from_millis(5000));
+        proxy.method_call("org.bluez.Adapter1", "StopDiscovery", ()).map_err(|e| e.to_string())
+    }
+
+    fn set_powered(&self, powered: bool) -> Result<(), String> {
+        let conn = Self::connection().ok_or("D-Bus unavailable")?;
+        let proxy = conn.with_proxy("org.bluez", &self.adapter_path, Duration::from_millis(5000));
+        proxy.set("org.bluez.Adapter1", "Powered", powered).map_err(|e| e.to_string())
+    }
+
+    fn powered(&self) -> bool {
+        let Some(conn) = Self::connection() else { return false };
+        let proxy = conn.with_proxy("org.bluez", &self.adapter_path, Duration::from_millis(2000));
+        proxy.get::<bool>("org.bluez.Adapter1", "Powered").unwrap_or(false)
+    }
+}
+
+/// A destructive session/power action awaiting confirmation from the user.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PowerAction {
+    Suspend,
+    Hibernate,
+    Reboot,
+    PowerOff,
+}
+
+impl PowerAction {
+    fn label(self) -> &'static str {
+        match self {
+            PowerAction::Suspend => "Suspend",
+            PowerAction::Hibernate => "Hibernate",
+            PowerAction::Reboot => "Reboot",
+            PowerAction::PowerOff => "Power Off",
+        }
+    }
+}
+
+/// Session/power controls via systemd-logind's `org.freedesktop.login1`
+/// system-bus service, falling back to `systemctl`/`loginctl` when logind's
+/// D-Bus manager isn't reachable (e.g. inside a container).
+struct PowerManager;
+
+impl PowerManager {
+    fn connection() -> Option<Connection> {
+        Connection::new_system().ok()
+    }
+
+    fn manager_call(method: &str) -> Result<(), String> {
+        let conn = Self::connection().ok_or("D-Bus unavailable")?;
+        let proxy = conn.with_proxy("org.freedesktop.login1", "/org/freedesktop/login1", Duration::from_millis(5000));
+        proxy.method_call("org.freedesktop.login1.Manager", method, (false,)).map_err(|e| e.to_string())
+    }
+
+    fn run_fallback(program: &str, args: &[&str]) -> Result<(), String> {
+        Command::new(program).args(args).status().map_err(|e| e.to_string()).and_then(|status| {
+            if status.success() { Ok(()) } else { Err(format!("{} exited with {}", program, status)) }
+        })
+    }
+
+    fn perform(&self, action: PowerAction) -> Result<(), String> {
+        let (method, fallback_args) = match action {
+            PowerAction::Suspend => ("Suspend", ["systemctl", "suspend"]),
+            PowerAction::Hibernate => ("Hibernate", ["systemctl", "hibernate"]),
+            PowerAction::Reboot => ("Reboot", ["systemctl", "reboot"]),
+            PowerAction::PowerOff => ("PowerOff", ["systemctl", "poweroff"]),
+        };
+        Self::manager_call(method).or_else(|_| Self::run_fallback(fallback_args[0], &fallback_args[1..]))
+    }
+
+    /// Locks the current session, preferring `LockSession` for the session
+    /// named by `XDG_SESSION_ID` and falling back to `loginctl lock-session`.
+    fn lock_session(&self) -> Result<(), String> {
+        if let Ok(session_id) = std::env::var("XDG_SESSION_ID") {
+            if let Some(conn) = Self::connection() {
+                let proxy = conn.with_proxy("org.freedesktop.login1", "/org/freedesktop/login1", Duration::from_millis(5000));
+                let result: Result<(), dbus::Error> = proxy.method_call("org.freedesktop.login1.Manager", "LockSession", (session_id,));
+                if result.is_ok() {
+                    return Ok(());
+                }
+            }
+        }
+        Self::run_fallback("loginctl", &["lock-session"])
+    }
+}
+
+/// Urgency hint from the `urgency` byte in a `Notify` call's hints map.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Urgency {
+    Low,
+    Normal,
+    Critical,
+}
+
+impl Urgency {
+    fn from_hint_byte(byte: u8) -> Self {
+        match byte {
+            0 => Urgency::Low,
+            2 => Urgency::Critical,
+            _ => Urgency::Normal,
+        }
+    }
+
+    fn color(self) -> Color32 {
+        match self {
+            Urgency::Low => Color32::GRAY,
+            Urgency::Normal => Color32::YELLOW,
+            Urgency::Critical => Color32::LIGHT_RED,
+        }
+    }
+}
+
+/// One notification, whether delivered over D-Bus by another app or raised
+/// internally by the launcher itself.
+#[derive(Clone)]
+struct Notification {
+    id: u32,
+    app: String,
+    summary: String,
+    body: String,
+    urgency: Urgency,
+    timestamp: String,
+    expires_at: Option<Instant>,
+    /// (action_key, display label) pairs decoded from `Notify`'s flat
+    /// `["key1", "label1", "key2", "label2", ...]` actions array.
+    actions: Vec<(String, String)>,
+}
+
+/// Pairs up `Notify`'s flat actions array into (action_key, label) tuples,
+/// dropping a trailing unpaired entry rather than panicking on malformed input.
+fn pair_actions(actions: Vec<String>) -> Vec<(String, String)> {
+    actions.chunks_exact(2).map(|pair| (pair[0].clone(), pair[1].clone())).collect()
+}
+
+/// An incoming `Notify` call, as forwarded from the D-Bus thread to the UI.
+struct NotifyEvent {
+    notification: Notification,
+}
+
+/// Implements `org.freedesktop.Notifications` on the session bus, the same
+/// interface eww's notification scripts consume, so the launcher can act as
+/// the desktop's actual notification server instead of keeping a private
+/// log. Incoming `Notify` calls are forwarded to the UI thread over `mpsc`;
+/// dismissals flow the other way so the server can emit `NotificationClosed`.
+/// `CloseNotification` calls from the sending app arrive on `close_requests`
+/// so the UI thread can drop its copy before telling the server to emit the
+/// signal back. `action_sender` is the same kind of UI-to-D-Bus-thread relay
+/// as `close_sender`, but for emitting `ActionInvoked` when the user clicks
+/// one of a notification's action buttons.
+struct NotificationServer {
+    receiver: mpsc::Receiver<NotifyEvent>,
+    close_sender: mpsc::Sender<(u32, u32)>,
+    close_requests: mpsc::Receiver<u32>,
+    action_sender: mpsc::Sender<(u32, String)>,
+}
+
+impl NotificationServer {
+    /// Spawns the D-Bus server thread. `next_id` is shared with the UI
+    /// thread so internally-raised notifications and D-Bus ones never
+    /// collide on id.
+    fn spawn(next_id: Arc<AtomicU32>) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let (close_tx, close_rx) = mpsc::channel();
+        let (close_req_tx, close_req_rx) = mpsc::channel();
+        let (action_tx, action_rx) = mpsc::channel();
+        thread::spawn(move || {
+            if let Err(e) = Self::run(tx, close_rx, close_req_tx, action_rx, next_id) {
+                log::error!("Notification server failed: {}", e);
+            }
+        });
+        NotificationServer { receiver: rx, close_sender: close_tx, close_requests: close_req_rx, action_sender: action_tx }
+    }
+
+    fn run(
+        tx: mpsc::Sender<NotifyEvent>,
+        close_rx: mpsc::Receiver<(u32, u32)>,
+        close_req_tx: mpsc::Sender<u32>,
+        action_rx: mpsc::Receiver<(u32, String)>,
+        next_id: Arc<AtomicU32>,
+    ) -> Result<(), dbus::Error> {
+        let conn = LocalConnection::new_session()?;
+        conn.request_name("org.freedesktop.Notifications", false, true, false)?;
+
+        let mut cr = Crossroads::new();
+        let closed_signal = cr.register("org.freedesktop.Notifications", |b| {
+            b.method(
+                "Notify",
+                ("app_name", "replaces_id", "app_icon", "summary", "body", "actions", "hints", "expire_timeout"),
+                ("id",),
+                move |_, _, (app_name, replaces_id, _app_icon, summary, body, actions, hints, expire_timeout):
+                    (String, u32, String, String, String, Vec<String>, HashMap<String, Variant<Box<dyn RefArg>>>, i32)| {
+                    let urgency = hints.get("urgency")
+                        .and_then(|v| v.0.as_i64())
+                        .map(|b| Urgency::from_hint_byte(b as u8))
+                        .unwrap_or(Urgency::Normal);
+                    let id = if replaces_id != 0 { replaces_id } else { next_id.fetch_add(1, Ordering::Relaxed) };
+                    let expires_at = if expire_timeout > 0 {
+                        Some(Instant::now() + Duration::from_millis(expire_timeout as u64))
+                    } else if expire_timeout == 0 {
+                        None
+                    } else {
+                        Some(Instant::now() + Duration::from_secs(5))
+                    };
+                    let notification = Notification {
+                        id,
+                        app: app_name,
+                        summary,
+                        body,
+                        urgency,
+                        timestamp: BlueLauncher::get_current_time(),
+                        expires_at,
+                        actions: pair_actions(actions),
+                    };
+                    tx.send(NotifyEvent { notification }).ok();
+                    Ok((id,))
+                },
+            );
+            b.method("CloseNotification", ("id",), (), {
+                let close_req_tx = close_req_tx.clone();
+                move |_, _, (id,): (u32,)| {
+                    close_req_tx.send(id).ok();
+                    Ok(())
+                }
+            });
+            b.method("GetCapabilities", (), ("capabilities",), |_, _, ()| {
+                Ok((vec!["body".to_string(), "actions".to_string(), "persistence".to_string()],))
+            });
+            b.method("GetServerInformation", (), ("name", "vendor", "version", "spec_version"), |_, _, ()| {
+                Ok((
+                    "Blue Notifications".to_string(),
+                    "VoidArc-Studio".to_string(),
+                    env!("CARGO_PKG_VERSION").to_string(),
+                    "1.2".to_string(),
+                ))
+            });
+            b.signal::<(u32, u32), _>("NotificationClosed", ("id", "reason"));
+            b.signal::<(u32, String), _>("ActionInvoked", ("id", "action_key"));
+        });
+        cr.insert("/org/freedesktop/Notifications", &[closed_signal], ());
+
+        // Route incoming method calls to the registered interface.
+        conn.start_receive(dbus::message::MatchRule::new_method_call(), Box::new(move |msg, conn| {
+            cr.handle_message(msg, conn).ok();
+            true
+        }));
+
+        loop {
+            while let Ok((id, reason)) = close_rx.try_recv() {
+                let signal = dbus::Message::new_signal(
+                    "/org/freedesktop/Notifications",
+                    "org.freedesktop.Notifications",
+                    "NotificationClosed",
+                ).expect("valid signal path/interface/member").append2(id, reason);
+                conn.channel().send(signal).ok();
+            }
+            while let Ok((id, action_key)) = action_rx.try_recv() {
+                let signal = dbus::Message::new_signal(
+                    "/org/freedesktop/Notifications",
+                    "org.freedesktop.Notifications",
+                    "ActionInvoked",
+                ).expect("valid signal path/interface/member").append2(id, action_key);
+                conn.channel().send(signal).ok();
+            }
+            conn.process(Duration::from_millis(200))?;
+        }
+    }
+
+    /// Drains everything queued so far without blocking the render loop.
+    fn drain(&self) -> Vec<NotifyEvent> {
+        self.receiver.try_iter().collect()
+    }
+
+    /// Drains ids the sending apps asked to close via `CloseNotification`,
+    /// so the UI thread can remove them from `self.notifications`.
+    fn drain_close_requests(&self) -> Vec<u32> {
+        self.close_requests.try_iter().collect()
+    }
+
+    /// Tells the D-Bus thread to emit `ActionInvoked` for `id`/`action_key`,
+    /// reporting a clicked action button back to the sending app.
+    fn invoke_action(&self, id: u32, action_key: String) {
+        self.action_sender.send((id, action_key)).ok();
+    }
+
+    /// Tells the D-Bus thread to emit `NotificationClosed` for `id`.
+    fn close(&self, id: u32, reason: u32) {
+        self.close_sender.send((id, reason)).ok();
+    }
+}
+
+/// A launcher action that can be bound to a keyboard shortcut, following
+/// ReSet's accelerator model (bind a key chord to a named action).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Action {
+    LaunchBrowser,
+    LaunchGameLauncher,
+    LaunchTerminal,
+    LaunchSoftwareCenter,
+    VolumeUp,
+    VolumeDown,
+    BrightnessUp,
+    BrightnessDown,
+    ToggleWifi,
+    ToggleBluetooth,
+    ReadClipboard,
+    LockSession,
+}
+
+impl Action {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "launch_browser" => Action::LaunchBrowser,
+            "launch_game_launcher" => Action::LaunchGameLauncher,
+            "launch_terminal" => Action::LaunchTerminal,
+            "launch_software_center" => Action::LaunchSoftwareCenter,
+            "volume_up" => Action::VolumeUp,
+            "volume_down" => Action::VolumeDown,
+            "brightness_up" => Action::BrightnessUp,
+            "brightness_down" => Action::BrightnessDown,
+            "toggle_wifi" => Action::ToggleWifi,
+            "toggle_bluetooth" => Action::ToggleBluetooth,
+            "read_clipboard" => Action::ReadClipboard,
+            "lock_session" => Action::LockSession,
+            _ => return None,
+        })
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Action::LaunchBrowser => "Launch browser",
+            Action::LaunchGameLauncher => "Launch games",
+            Action::LaunchTerminal => "Launch terminal",
+            Action::LaunchSoftwareCenter => "Launch software center",
+            Action::VolumeUp => "Volume up",
+            Action::VolumeDown => "Volume down",
+            Action::BrightnessUp => "Brightness up",
+            Action::BrightnessDown => "Brightness down",
+            Action::ToggleWifi => "Toggle Wi-Fi",
+            Action::ToggleBluetooth => "Toggle Bluetooth",
+            Action::ReadClipboard => "Read clipboard",
+            Action::LockSession => "Lock session",
+        }
+    }
+}
+
+impl ModMask {
+    fn matches(self, modifiers: &egui::Modifiers) -> bool {
+        self.contains(ModMask::SHIFT) == modifiers.shift
+            && self.contains(ModMask::CTRL) == modifiers.ctrl
+            && self.contains(ModMask::ALT) == modifiers.alt
+            && self.contains(ModMask::LOGO) == modifiers.mac_cmd
+    }
+}
+
+/// Resolves a chord's trailing key name ("V", "Escape", "F1", ...) to an
+/// `egui::Key`. Covers the letters/digits and named keys this launcher's
+/// default bindings use; unknown names fail the whole chord to parse.
+fn egui_key_from_name(name: &str) -> Option<egui::Key> {
+    if name.len() == 1 {
+        let c = name.chars().next().unwrap().to_ascii_uppercase();
+        if c.is_ascii_alphabetic() {
+            return egui::Key::from_name(&c.to_string());
+        }
+        if c.is_ascii_digit() {
+            return egui::Key::from_name(name);
+        }
+    }
+    match name {
+        "Escape" => Some(egui::Key::Escape),
+        "Tab" => Some(egui::Key::Tab),
+        "Space" => Some(egui::Key::Space),
+        "Return" | "Enter" => Some(egui::Key::Enter),
+        "Up" => Some(egui::Key::ArrowUp),
+        "Down" => Some(egui::Key::ArrowDown),
+        "Left" => Some(egui::Key::ArrowLeft),
+        "Right" => Some(egui::Key::ArrowRight),
+        _ if name.starts_with('F') && name[1..].parse::<u32>().is_ok() => egui::Key::from_name(name),
+        _ => None,
+    }
+}
+
+/// Parses a chord string (`"Ctrl+Shift+V"`) into a (modifier mask, key)
+/// lookup key. Returns `None` if the trailing key name isn't recognized.
+fn chord_to_shortcut(chord: &str) -> Option<(u8, egui::Key)> {
+    let mut mask = ModMask::empty();
+    let mut key = None;
+    for part in chord.split('+') {
+        match part {
+            "Super" | "Logo" | "Cmd" | "Command" => mask |= ModMask::LOGO,
+            "Shift" => mask |= ModMask::SHIFT,
+            "Ctrl" | "Control" => mask |= ModMask::CTRL,
+            "Alt" => mask |= ModMask::ALT,
+            name => key = egui_key_from_name(name),
+        }
+    }
+    key.map(|key| (mask.bits(), key))
+}
+
+/// Renders a (modifier mask, key) lookup key back into a chord string like
+/// `"Ctrl+Shift+V"`, for the help overlay.
+fn format_chord(mask: u8, key: egui::Key) -> String {
+    let mask = ModMask::from_bits_truncate(mask);
+    let mut parts = Vec::new();
+    if mask.contains(ModMask::CTRL) { parts.push("Ctrl".to_string()); }
+    if mask.contains(ModMask::ALT) { parts.push("Alt".to_string()); }
+    if mask.contains(ModMask::SHIFT) { parts.push("Shift".to_string()); }
+    if mask.contains(ModMask::LOGO) { parts.push("Super".to_string()); }
+    parts.push(format!("{:?}", key));
+    parts.join("+")
+}
+
+/// Loads the `[shortcuts]` table from config.toml, mapping key chords to
+/// `Action`s so power users can drive the launcher without the mouse.
+struct Shortcuts {
+    bindings: HashMap<(u8, egui::Key), Action>,
+}
+
+impl Shortcuts {
+    fn load(config: &Value) -> Self {
+        // Seeded with sensible defaults so a config with no `[shortcuts]`
+        // table still has working accelerators.
+        let mut bindings: HashMap<(u8, egui::Key), Action> = [
+            ("Ctrl+B", Action::LaunchBrowser),
+            ("Ctrl+G", Action::LaunchGameLauncher),
+            ("Ctrl+T", Action::LaunchTerminal),
+            ("Ctrl+S", Action::LaunchSoftwareCenter),
+            ("Ctrl+W", Action::ToggleWifi),
+            ("Ctrl+L", Action::ToggleBluetooth),
+            ("Ctrl+Up", Action::VolumeUp),
+            ("Ctrl+Down", Action::VolumeDown),
+            ("Ctrl+Shift+Up", Action::BrightnessUp),
+            ("Ctrl+Shift+Down", Action::BrightnessDown),
+            ("Ctrl+C", Action::ReadClipboard),
+            ("Ctrl+Shift+L", Action::LockSession),
+        ]
+        .into_iter()
+        .filter_map(|(chord, action)| chord_to_shortcut(chord).map(|key| (key, action)))
+        .collect();
+
+        if let Some(table) = config.get("shortcuts").and_then(|v| v.as_table()) {
+            for (chord, action_name) in table {
+                if let (Some(key), Some(action)) = (
+                    chord_to_shortcut(chord),
+                    action_name.as_str().and_then(Action::from_name),
+                ) {
+                    bindings.insert(key, action);
+                }
+            }
+        }
+
+        Shortcuts { bindings }
+    }
+
+    /// Renders the active bindings as `"Ctrl+B — Launch browser"` lines for
+    /// the help overlay, sorted by action so the list is stable across runs.
+    fn describe(&self) -> Vec<String> {
+        let mut lines: Vec<(String, String)> = self.bindings.iter()
+            .map(|((mask, key), action)| (format_chord(*mask, *key), action.label().to_string()))
+            .collect();
+        lines.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        lines.into_iter().map(|(chord, label)| format!("{} — {}", chord, label)).collect()
+    }
+
+    /// Chords whose key was pressed this frame with matching modifiers.
+    fn triggered(&self, ctx: &Context) -> Vec<Action> {
+        ctx.input(|input| {
+            self.bindings.iter()
+                .filter(|((mask, key), _)| {
+                    input.key_pressed(*key) && ModMask::from_bits_truncate(*mask).matches(&input.modifiers)
+                })
+                .map(|(_, action)| *action)
+                .collect()
+        })
+    }
+}
 
 struct BlueLauncher {
     config: Value,
@@ -20,7 +744,24 @@ struct BlueLauncher {
     battery_status: String,
     time: String,
     clipboard_content: String,
-    notifications: Vec<String>,
+    notifications: Vec<Notification>,
+    notification_server: NotificationServer,
+    next_notification_id: Arc<AtomicU32>,
+    bluetooth: BluetoothManager,
+    bluetooth_devices: Vec<BluetoothDevice>,
+    poller: Poller,
+    cpu_usage: f32,
+    memory_usage: f32,
+    wifi_networks: Vec<WifiNetwork>,
+    connected_ssid: Option<String>,
+    wifi_password_prompt: Option<String>,
+    wifi_password_input: String,
+    power: PowerManager,
+    pending_power_action: Option<PowerAction>,
+    shortcuts: Shortcuts,
+    help_overlay_visible: bool,
+    bluetooth_panel_open: bool,
+    wifi_panel_open: bool,
 }
 
 impl BlueLauncher {
@@ -67,6 +808,12 @@ impl BlueLauncher {
         // Initialize system state
         let battery_status = Self::get_battery_status();
         let time = Self::get_current_time();
+        let bluetooth = BluetoothManager::new();
+        let bluetooth_enabled = bluetooth.powered();
+        let poller = Poller::spawn(&config);
+        let next_notification_id = Arc::new(AtomicU32::new(1));
+        let notification_server = NotificationServer::spawn(next_notification_id.clone());
+        let shortcuts = Shortcuts::load(&config);
 
         BlueLauncher {
             config,
@@ -76,11 +823,28 @@ impl BlueLauncher {
             brightness: 0.5,
             volume: 0.5,
             wifi_enabled: Self::get_wifi_status(),
-            bluetooth_enabled: Self::get_bluetooth_status(),
+            bluetooth_enabled,
             battery_status,
             time,
             clipboard_content: String::new(),
             notifications: Vec::new(),
+            notification_server,
+            next_notification_id,
+            bluetooth,
+            bluetooth_devices: Vec::new(),
+            poller,
+            cpu_usage: 0.0,
+            memory_usage: 0.0,
+            wifi_networks: Vec::new(),
+            connected_ssid: None,
+            wifi_password_prompt: None,
+            wifi_password_input: String::new(),
+            power: PowerManager,
+            pending_power_action: None,
+            shortcuts,
+            help_overlay_visible: false,
+            bluetooth_panel_open: false,
+            wifi_panel_open: false,
         }
     }
 
@@ -93,16 +857,16 @@ impl BlueLauncher {
             match Command::new(app_path).spawn() {
                 Ok(child) => {
                     self.running_apps.insert(app.to_string(), child);
-                    self.notifications.push(format!("Launched {}", app));
+                    self.push_notification(format!("Launched {}", app));
                     true
                 }
                 Err(e) => {
-                    self.notifications.push(format!("Failed to launch {}: {}", app, e));
+                    self.push_notification(format!("Failed to launch {}: {}", app, e));
                     false
                 }
             }
         } else {
-            self.notifications.push(format!("No path for app {} in config", app));
+            self.push_notification(format!("No path for app {} in config", app));
             false
         }
     }
@@ -115,7 +879,7 @@ impl BlueLauncher {
         .arg(format!("{}%", brightness_percent))
         .spawn()
         .ok();
-        self.notifications.push(format!("Brightness set to {}%", brightness_percent));
+        self.push_notification(format!("Brightness set to {}%", brightness_percent));
     }
 
     fn adjust_volume(&mut self, delta: f32) {
@@ -125,7 +889,7 @@ impl BlueLauncher {
         .args(["set-volume", "@DEFAULT_SINK@", &format!("{}%", volume_percent)])
         .spawn()
         .ok();
-        self.notifications.push(format!("Volume set to {}%", volume_percent));
+        self.push_notification(format!("Volume set to {}%", volume_percent));
     }
 
     fn toggle_wifi(&mut self) {
@@ -135,17 +899,69 @@ impl BlueLauncher {
         .args(["radio", "wifi", status])
         .spawn()
         .ok();
-        self.notifications.push(format!("Wi-Fi turned {}", status));
+        self.push_notification(format!("Wi-Fi turned {}", status));
     }
 
     fn toggle_bluetooth(&mut self) {
         self.bluetooth_enabled = !self.bluetooth_enabled;
+        if let Err(e) = self.bluetooth.set_powered(self.bluetooth_enabled) {
+            self.push_notification(format!("Failed to toggle Bluetooth: {}", e));
+            return;
+        }
         let status = if self.bluetooth_enabled { "on" } else { "off" };
-        Command::new("bluetoothctl")
-        .args(["power", status])
-        .spawn()
-        .ok();
-        self.notifications.push(format!("Bluetooth turned {}", status));
+        self.push_notification(format!("Bluetooth turned {}", status));
+    }
+
+    /// Refreshes the device list shown in the Bluetooth panel; called when
+    /// the panel is expanded and after connect/disconnect actions.
+    fn refresh_bluetooth_devices(&mut self) {
+        self.bluetooth_devices = self.bluetooth.devices();
+    }
+
+    fn connect_bluetooth_device(&mut self, path: &str, name: &str) {
+        match self.bluetooth.connect(path) {
+            Ok(()) => self.push_notification(format!("Connected to {}", name)),
+            Err(e) => self.push_notification(format!("Failed to connect to {}: {}", name, e)),
+        }
+        self.refresh_bluetooth_devices();
+    }
+
+    fn disconnect_bluetooth_device(&mut self, path: &str, name: &str) {
+        match self.bluetooth.disconnect(path) {
+            Ok(()) => self.push_notification(format!("Disconnected from {}", name)),
+            Err(e) => self.push_notification(format!("Failed to disconnect from {}: {}", name, e)),
+        }
+        self.refresh_bluetooth_devices();
+    }
+
+    /// Refreshes the network list shown in the Wi-Fi panel; called when the
+    /// panel is expanded and after a connect attempt.
+    fn refresh_wifi_networks(&mut self) {
+        self.wifi_networks = scan_wifi_networks();
+        self.connected_ssid = current_wifi_ssid();
+    }
+
+    /// Connects to `ssid` via `nmcli dev wifi connect`, passing `password`
+    /// along when the network is secured.
+    fn connect_wifi_network(&mut self, ssid: &str, password: Option<&str>) {
+        let mut args = vec!["dev", "wifi", "connect", ssid];
+        if let Some(password) = password {
+            args.push("password");
+            args.push(password);
+        }
+        match Command::new("nmcli").args(&args).output() {
+            Ok(output) if output.status.success() => {
+                self.push_notification(format!("Connected to {}", ssid));
+            }
+            Ok(output) => {
+                let error = String::from_utf8_lossy(&output.stderr);
+                self.push_notification(format!("Failed to connect to {}: {}", ssid, error.trim()));
+            }
+            Err(e) => self.push_notification(format!("Failed to connect to {}: {}", ssid, e)),
+        }
+        self.wifi_password_prompt = None;
+        self.wifi_password_input.clear();
+        self.refresh_wifi_networks();
     }
 
     fn get_battery_status() -> String {
@@ -177,18 +993,72 @@ impl BlueLauncher {
         .unwrap_or(false)
     }
 
-    fn get_bluetooth_status() -> bool {
-        Command::new("bluetoothctl")
-        .args(["show"])
-        .output()
-        .map(|output| String::from_utf8_lossy(&output.stdout).contains("Powered: yes"))
-        .unwrap_or(false)
-    }
-
     fn read_clipboard(&mut self) {
         if let Ok(output) = Command::new("wl-paste").output() {
             self.clipboard_content = String::from_utf8_lossy(&output.stdout).to_string();
-            self.notifications.push("Clipboard content updated".to_string());
+            self.push_notification("Clipboard content updated".to_string());
+        }
+    }
+
+    /// Raises one of the launcher's own internal notifications, using the
+    /// same `Notification` type and id space as ones delivered over D-Bus.
+    fn push_notification(&mut self, summary: String) {
+        let id = self.next_notification_id.fetch_add(1, Ordering::Relaxed);
+        self.notifications.push(Notification {
+            id,
+            app: "Blue Launcher".to_string(),
+            summary,
+            body: String::new(),
+            urgency: Urgency::Normal,
+            timestamp: Self::get_current_time(),
+            expires_at: Some(Instant::now() + Duration::from_secs(5)),
+            actions: Vec::new(),
+        });
+    }
+
+    /// Dismisses a notification by id, notifying the D-Bus server so it can
+    /// emit `NotificationClosed` to whichever app sent it.
+    fn dismiss_notification(&mut self, id: u32) {
+        self.notifications.retain(|n| n.id != id);
+        self.notification_server.close(id, 2); // reason 2: dismissed by the user
+    }
+
+    /// Arms a confirmation prompt for a destructive power action; the UI
+    /// asks again before `confirm_power_action` actually runs it.
+    fn request_power_action(&mut self, action: PowerAction) {
+        self.pending_power_action = Some(action);
+    }
+
+    fn confirm_power_action(&mut self) {
+        let Some(action) = self.pending_power_action.take() else { return };
+        match self.power.perform(action) {
+            Ok(()) => self.push_notification(format!("{} initiated", action.label())),
+            Err(e) => self.push_notification(format!("Failed to {}: {}", action.label().to_lowercase(), e)),
+        }
+    }
+
+    fn lock_session(&mut self) {
+        match self.power.lock_session() {
+            Ok(()) => self.push_notification("Session locked".to_string()),
+            Err(e) => self.push_notification(format!("Failed to lock session: {}", e)),
+        }
+    }
+
+    /// Runs the `BlueLauncher` method bound to a shortcut-triggered action.
+    fn dispatch_action(&mut self, action: Action) {
+        match action {
+            Action::LaunchBrowser => { self.launch_app("browser"); }
+            Action::LaunchGameLauncher => { self.launch_app("game_launcher"); }
+            Action::LaunchTerminal => { self.launch_app("terminal"); }
+            Action::LaunchSoftwareCenter => { self.launch_app("software_center"); }
+            Action::VolumeUp => self.adjust_volume(0.1),
+            Action::VolumeDown => self.adjust_volume(-0.1),
+            Action::BrightnessUp => self.adjust_brightness(0.1),
+            Action::BrightnessDown => self.adjust_brightness(-0.1),
+            Action::ToggleWifi => self.toggle_wifi(),
+            Action::ToggleBluetooth => self.toggle_bluetooth(),
+            Action::ReadClipboard => self.read_clipboard(),
+            Action::LockSession => self.lock_session(),
         }
     }
 }
@@ -201,6 +1071,53 @@ impl eframe::App for BlueLauncher {
         // Update time
         self.time = Self::get_current_time();
 
+        // Drain whatever the polling threads have produced since the last
+        // frame instead of reading battery/volume/brightness/wifi inline.
+        for update in self.poller.drain() {
+            match update {
+                StatusUpdate::Battery(status) => self.battery_status = status,
+                StatusUpdate::Volume(level) => self.volume = level,
+                StatusUpdate::Brightness(level) => self.brightness = level,
+                StatusUpdate::Wifi(enabled) => self.wifi_enabled = enabled,
+                StatusUpdate::CpuUsage(usage) => self.cpu_usage = usage,
+                StatusUpdate::MemoryUsage(usage) => self.memory_usage = usage,
+            }
+        }
+
+        // Pull in whatever other apps have sent us via `org.freedesktop.Notifications`.
+        for event in self.notification_server.drain() {
+            self.notifications.retain(|n| n.id != event.notification.id);
+            self.notifications.push(event.notification);
+        }
+
+        // Remove notifications the sending app asked to close via
+        // `CloseNotification`, telling the server to emit `NotificationClosed`
+        // (reason 3: closed by a `CloseNotification` call).
+        for id in self.notification_server.drain_close_requests() {
+            self.notifications.retain(|n| n.id != id);
+            self.notification_server.close(id, 3);
+        }
+
+        // Expire notifications whose timeout has elapsed, telling the
+        // server to emit `NotificationClosed` for each one (reason 1: expired).
+        let now = Instant::now();
+        let (expired, live): (Vec<Notification>, Vec<Notification>) = self.notifications.drain(..)
+            .partition(|n| n.expires_at.is_some_and(|t| now >= t));
+        self.notifications = live;
+        for notification in expired {
+            self.notification_server.close(notification.id, 1);
+        }
+
+        // Consume this frame's key events: dispatch whatever shortcuts
+        // matched, and toggle the help overlay on a bare `?`.
+        let triggered = self.shortcuts.triggered(ctx);
+        for action in triggered {
+            self.dispatch_action(action);
+        }
+        if ctx.input(|i| i.events.iter().any(|e| matches!(e, egui::Event::Text(t) if t == "?"))) {
+            self.help_overlay_visible = !self.help_overlay_visible;
+        }
+
         // Apply custom styling
         let mut style = (*ctx.style()).clone();
         style.visuals.panel_fill = Color32::from_rgb(20, 20, 30);
@@ -228,12 +1145,15 @@ impl eframe::App for BlueLauncher {
 
             ui.add_space(20.0);
 
-            // Settings panel
-            ui.collapsing(RichText::new("⚙️ Settings").size(24.0), |ui| {
+            // Settings panel. Polling for battery/volume/brightness/wifi/cpu/
+            // memory only actually runs while this section is expanded.
+            let settings = ui.collapsing(RichText::new("⚙️ Settings").size(24.0), |ui| {
                 // System Info
                 ui.label(RichText::new(format!("Distribution: {}", self.distro)).size(16.0));
                 ui.label(RichText::new(format!("Time: {}", self.time)).size(16.0));
                 ui.label(RichText::new(format!("Battery: {}", self.battery_status)).size(16.0));
+                ui.label(RichText::new(format!("CPU load: {:.2}", self.cpu_usage)).size(16.0));
+                ui.label(RichText::new(format!("Memory: {:.0}%", self.memory_usage * 100.0)).size(16.0));
 
                 // Brightness
                 ui.horizontal(|ui| {
@@ -274,6 +1194,71 @@ impl eframe::App for BlueLauncher {
                         self.toggle_bluetooth();
                     }
                 });
+                let bluetooth_panel = ui.collapsing(RichText::new("Bluetooth Devices").size(18.0), |ui| {
+                    if ui.button("Scan").clicked() {
+                        self.bluetooth.start_discovery().ok();
+                    }
+                    for device in self.bluetooth_devices.clone() {
+                        ui.horizontal(|ui| {
+                            let status = if device.connected { "🟢" } else if device.paired { "⚪" } else { "" };
+                            let battery = device.battery_percent.map(|p| format!(" ({}%)", p)).unwrap_or_default();
+                            ui.label(format!("{} {}{}", status, device.alias, battery));
+                            if device.connected {
+                                if ui.button("Disconnect").clicked() {
+                                    self.disconnect_bluetooth_device(&device.path, &device.alias);
+                                }
+                            } else if ui.button("Connect").clicked() {
+                                self.connect_bluetooth_device(&device.path, &device.alias);
+                            }
+                        });
+                    }
+                });
+                let bluetooth_panel_open = bluetooth_panel.body_returned.is_some();
+                if bluetooth_panel_open && !self.bluetooth_panel_open {
+                    self.refresh_bluetooth_devices();
+                }
+                self.bluetooth_panel_open = bluetooth_panel_open;
+
+                let wifi_panel = ui.collapsing(RichText::new("Wi-Fi Networks").size(18.0), |ui| {
+                    if ui.button("Scan").clicked() {
+                        self.refresh_wifi_networks();
+                    }
+                    for network in self.wifi_networks.clone() {
+                        ui.horizontal(|ui| {
+                            let status = if self.connected_ssid.as_deref() == Some(network.ssid.as_str()) { "🟢" } else { "" };
+                            let lock = if network.secured { "🔒" } else { "" };
+                            ui.label(format!("{} {} {} ({}%)", status, network.ssid, lock, network.signal));
+                            if self.connected_ssid.as_deref() == Some(network.ssid.as_str()) {
+                                ui.label("Connected");
+                            } else if network.secured {
+                                if ui.button("Connect").clicked() {
+                                    self.wifi_password_prompt = Some(network.ssid.clone());
+                                    self.wifi_password_input.clear();
+                                }
+                            } else if ui.button("Connect").clicked() {
+                                self.connect_wifi_network(&network.ssid, None);
+                            }
+                        });
+                    }
+                    if let Some(ssid) = self.wifi_password_prompt.clone() {
+                        ui.horizontal(|ui| {
+                            ui.label(format!("Password for {}:", ssid));
+                            ui.add(egui::TextEdit::singleline(&mut self.wifi_password_input).password(true));
+                            if ui.button("Connect").clicked() {
+                                self.connect_wifi_network(&ssid, Some(&self.wifi_password_input.clone()));
+                            }
+                            if ui.button("Cancel").clicked() {
+                                self.wifi_password_prompt = None;
+                                self.wifi_password_input.clear();
+                            }
+                        });
+                    }
+                });
+                let wifi_panel_open = wifi_panel.body_returned.is_some();
+                if wifi_panel_open && !self.wifi_panel_open {
+                    self.refresh_wifi_networks();
+                }
+                self.wifi_panel_open = wifi_panel_open;
 
                 // Clipboard
                 ui.horizontal(|ui| {
@@ -287,7 +1272,7 @@ impl eframe::App for BlueLauncher {
                 // KDE Wallet
                 if ui.button("Open KDE Wallet").clicked() {
                     Command::new("kwalletmanager5").spawn().ok();
-                    self.notifications.push("Opened KDE Wallet".to_string());
+                    self.push_notification("Opened KDE Wallet".to_string());
                 }
 
                 // Package Manager
@@ -304,21 +1289,97 @@ impl eframe::App for BlueLauncher {
                     .arg(pkg_manager)
                     .spawn()
                     .ok();
-                    self.notifications.push(format!("Opened {}", pkg_manager));
+                    self.push_notification(format!("Opened {}", pkg_manager));
                 }
+
+                // Session / power controls
+                ui.collapsing(RichText::new("Power").size(18.0), |ui| {
+                    ui.horizontal(|ui| {
+                        if ui.button("Lock").clicked() {
+                            self.lock_session();
+                        }
+                        if ui.button("Suspend").clicked() {
+                            self.request_power_action(PowerAction::Suspend);
+                        }
+                        if ui.button("Hibernate").clicked() {
+                            self.request_power_action(PowerAction::Hibernate);
+                        }
+                        if ui.button("Reboot").clicked() {
+                            self.request_power_action(PowerAction::Reboot);
+                        }
+                        if ui.button("Power Off").clicked() {
+                            self.request_power_action(PowerAction::PowerOff);
+                        }
+                    });
+                    if let Some(action) = self.pending_power_action {
+                        ui.horizontal(|ui| {
+                            ui.label(format!("{}? This cannot be undone.", action.label()));
+                            if ui.button("Confirm").clicked() {
+                                self.confirm_power_action();
+                            }
+                            if ui.button("Cancel").clicked() {
+                                self.pending_power_action = None;
+                            }
+                        });
+                    }
+                });
             });
+            self.poller.set_settings_visible(settings.body_returned.is_some());
 
             // Notifications
             ui.collapsing(RichText::new("🔔 Notifications").size(24.0), |ui| {
+                let mut dismissed = Vec::new();
+                let mut invoked = Vec::new();
                 for notification in &self.notifications {
-                    ui.label(RichText::new(notification).size(16.0).color(Color32::YELLOW));
+                    ui.horizontal(|ui| {
+                        let header = if notification.body.is_empty() {
+                            format!("[{}] {}", notification.app, notification.summary)
+                        } else {
+                            format!("[{}] {} — {}", notification.app, notification.summary, notification.body)
+                        };
+                        ui.label(RichText::new(header).size(16.0).color(notification.urgency.color()));
+                        ui.label(RichText::new(&notification.timestamp).size(12.0).color(Color32::GRAY));
+                        for (action_key, label) in &notification.actions {
+                            if ui.button(label).clicked() {
+                                invoked.push((notification.id, action_key.clone()));
+                            }
+                        }
+                        if ui.button("✕").clicked() {
+                            dismissed.push(notification.id);
+                        }
+                    });
+                }
+                for (id, action_key) in invoked {
+                    self.notification_server.invoke_action(id, action_key);
+                }
+                for id in dismissed {
+                    self.dismiss_notification(id);
                 }
                 if ui.button("Clear Notifications").clicked() {
-                    self.notifications.clear();
+                    let ids: Vec<u32> = self.notifications.iter().map(|n| n.id).collect();
+                    for id in ids {
+                        self.dismiss_notification(id);
+                    }
                 }
             });
         });
 
+        // Help overlay: discoverable list of the active shortcut bindings,
+        // toggled with `?` so power users aren't left guessing the chords.
+        if self.help_overlay_visible {
+            egui::Window::new("Keyboard Shortcuts")
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    for line in self.shortcuts.describe() {
+                        ui.label(RichText::new(line).size(16.0));
+                    }
+                    if ui.button("Close").clicked() {
+                        self.help_overlay_visible = false;
+                    }
+                });
+        }
+
         // Request repaint for real-time updates
         ctx.request_repaint();
     }
@@ -1,4 +1,5 @@
 pub mod compositor;
+pub mod keybind;
 pub mod launcher;
 
 use toml::Value;
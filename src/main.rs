@@ -1,4 +1,5 @@
 mod compositor;
+mod keybind;
 mod launcher;
 
 use std::env;